@@ -6,6 +6,7 @@ import {
   Keypair,
   SystemProgram,
   SYSVAR_RENT_PUBKEY,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
   Transaction,
   sendAndConfirmTransaction,
   Connection,
@@ -14,11 +15,88 @@ import {
   TOKEN_PROGRAM_ID,
   ASSOCIATED_TOKEN_PROGRAM_ID,
   createAssociatedTokenAccountInstruction,
+  createAssociatedTokenAccountIdempotentInstruction,
   getAssociatedTokenAddress,
   createMintToInstruction,
+  NATIVE_MINT,
 } from '@solana/spl-token';
 import { TOKEN_2022_PROGRAM_ID } from '@solana/spl-token-2022';
 
+// Optional launch settings for createTokenProject
+export interface CreateTokenProjectOptions {
+  maxWalletBps?: number;
+  maxBuyPerTx?: anchor.BN;
+  buyCooldownSecs?: number;
+  uri?: string;
+  // Extra TokenMetadata fields, e.g. { key: 'website', value: 'https://...' }
+  extraMetadata?: MetadataField[];
+  // Mint decimals, 0-9 (defaults to 9)
+  decimals?: number;
+  // SPL token to price the launch in, e.g. USDC (defaults to native SOL)
+  quoteMint?: PublicKey;
+  // Tokens the creator buys from the curve in the launch instruction itself
+  initialBuyAmount?: anchor.BN;
+  // Max lamports the dev-buy may cost, including the platform fee
+  maxSolCost?: anchor.BN;
+  // Creator allocation minted into a vesting escrow, outside the curve
+  vesting?: VestingSchedule;
+  // Unix time buys open (defaults to immediately)
+  launchAt?: anchor.BN;
+  // Whitelist-only phase between launchAt and public trading
+  whitelist?: WhitelistPhase;
+  // Buy fee that starts at startFeeBps in the launch slot and decays to the normal fee
+  launchFee?: { startFeeBps: number; decaySlots: anchor.BN };
+  // Open with a batch auction that takes SOL commitments until this unix time
+  auctionEndsAt?: anchor.BN;
+  // Refund holders if the reserve has not reached the minimum by the deadline
+  minRaise?: MinRaise;
+}
+
+export interface MinRaise {
+  // Lamports the reserve must reach
+  amount: anchor.BN;
+  deadline: anchor.BN;
+}
+
+// Limit order settings for placeOrder
+export interface LimitOrderOptions {
+  // Max lamports a buy may cost, escrowed until the order fills (required for buys)
+  maxSolCost?: anchor.BN;
+  // Least lamports a sell may pay out after fees
+  minProceeds?: anchor.BN;
+  // Lamports paid to the keeper that executes the order
+  keeperFee?: anchor.BN;
+  // Unix time after which the order can no longer fill
+  expiresAt: anchor.BN;
+}
+
+export interface WhitelistPhase {
+  // Merkle root over keccak256(wallet) leaves, pairs hashed in sorted order
+  merkleRoot: number[];
+  walletCap: anchor.BN;
+  publicOpenAt: anchor.BN;
+}
+
+export interface VestingSchedule {
+  amount: anchor.BN;
+  // Seconds after launch before anything unlocks
+  cliffSecs: anchor.BN;
+  // Seconds after launch until everything has unlocked
+  durationSecs: anchor.BN;
+}
+
+export interface MetadataField {
+  key: string;
+  value: string;
+}
+
+export interface ProjectMetadataUpdate {
+  name?: string;
+  symbol?: string;
+  uri?: string;
+  extraMetadata?: MetadataField[];
+}
+
 export class LaunchpadClient {
   private program: Program<BondCurveLaunchpad>;
   private connection: Connection;
@@ -66,6 +144,13 @@ export class LaunchpadClient {
     );
   }
 
+  async findExtraAccountMetaListPDA(mint: PublicKey): Promise<[PublicKey, number]> {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('extra-account-metas'), mint.toBuffer()],
+      this.program.programId
+    );
+  }
+
   async findBundleTrackerPDA(mint: PublicKey, wallet: PublicKey): Promise<[PublicKey, number]> {
     return PublicKey.findProgramAddressSync(
       [Buffer.from('bundle'), mint.toBuffer(), wallet.toBuffer()],
@@ -89,6 +174,194 @@ export class LaunchpadClient {
     );
   }
 
+  async findPenaltyVaultPDA(mint: PublicKey): Promise<[PublicKey, number]> {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('penalty_vault'), mint.toBuffer()],
+      this.program.programId
+    );
+  }
+
+  async findBundleClusterPDA(mint: PublicKey, clusterId: anchor.BN): Promise<[PublicKey, number]> {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('cluster'), mint.toBuffer(), clusterId.toArrayLike(Buffer, 'le', 8)],
+      this.program.programId
+    );
+  }
+
+  async findClusterMembershipPDA(mint: PublicKey, wallet: PublicKey): Promise<[PublicKey, number]> {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('membership'), mint.toBuffer(), wallet.toBuffer()],
+      this.program.programId
+    );
+  }
+
+  async findExemptAddressPDA(address: PublicKey): Promise<[PublicKey, number]> {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('exempt'), address.toBuffer()],
+      this.program.programId
+    );
+  }
+
+  // The allowlist entry for an address, or null if it has none
+  async getExemptAddressAccount(address: PublicKey): Promise<PublicKey | null> {
+    const [exemptPDA] = await this.findExemptAddressPDA(address);
+    const accountInfo = await this.connection.getAccountInfo(exemptPDA);
+    return accountInfo ? exemptPDA : null;
+  }
+
+  async findReferrerPDA(wallet: PublicKey): Promise<[PublicKey, number]> {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('referrer'), wallet.toBuffer()],
+      this.program.programId
+    );
+  }
+
+  // Rolling SOL volume used to pick the trader's fee tier
+  async findTraderStatsPDA(wallet: PublicKey): Promise<[PublicKey, number]> {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('trader_stats'), wallet.toBuffer()],
+      this.program.programId
+    );
+  }
+
+  // Running total of a wallet's whitelist-phase buys
+  async findWhitelistPurchasePDA(
+    mint: PublicKey,
+    wallet: PublicKey
+  ): Promise<[PublicKey, number]> {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('whitelist'), mint.toBuffer(), wallet.toBuffer()],
+      this.program.programId
+    );
+  }
+
+  // Escrow holding the creator's vesting allocation
+  async findVestingEscrowPDA(mint: PublicKey): Promise<[PublicKey, number]> {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('vesting'), mint.toBuffer()],
+      this.program.programId
+    );
+  }
+
+  // Batch auction that opens an auctioned project
+  async findLaunchAuctionPDA(mint: PublicKey): Promise<[PublicKey, number]> {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('auction'), mint.toBuffer()],
+      this.program.programId
+    );
+  }
+
+  // A wallet's SOL commitment to a launch auction
+  async findAuctionCommitmentPDA(
+    mint: PublicKey,
+    wallet: PublicKey
+  ): Promise<[PublicKey, number]> {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('commitment'), mint.toBuffer(), wallet.toBuffer()],
+      this.program.programId
+    );
+  }
+
+  // A wallet's limit order, told apart from its other orders by orderId
+  async findOrderPDA(
+    mint: PublicKey,
+    owner: PublicKey,
+    orderId: anchor.BN
+  ): Promise<[PublicKey, number]> {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('order'), mint.toBuffer(), owner.toBuffer(), orderId.toArrayLike(Buffer, 'le', 8)],
+      this.program.programId
+    );
+  }
+
+  // Graduation market cap configured for a quote mint
+  async findQuoteConfigPDA(quoteMint: PublicKey): Promise<[PublicKey, number]> {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('quote_config'), quoteMint.toBuffer()],
+      this.program.programId
+    );
+  }
+
+  // Token account holding the reserve of a project priced in an SPL token
+  async findQuoteVaultPDA(mint: PublicKey): Promise<[PublicKey, number]> {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('quote_vault'), mint.toBuffer()],
+      this.program.programId
+    );
+  }
+
+  // Quote accounts for a trade, or nulls when the project is priced in SOL
+  async getQuoteAccounts(mint: PublicKey, trader: PublicKey) {
+    const [projectPDA] = await this.findProjectPDA(mint);
+    const [feeVaultPDA] = await this.findFeeVaultPDA();
+    const project = await this.program.account.tokenProject.fetch(projectPDA);
+
+    if (!project.quoteMint) {
+      return {
+        quoteMint: null,
+        quoteVault: null,
+        traderQuoteAccount: null,
+        creatorQuoteAccount: null,
+        feeVaultQuoteAccount: null,
+        quoteTokenProgram: null,
+      };
+    }
+
+    // The quote mint may belong to either token program
+    const quoteMint = project.quoteMint as PublicKey;
+    const quoteMintInfo = await this.connection.getAccountInfo(quoteMint);
+    const quoteTokenProgram = quoteMintInfo!.owner;
+    const [quoteVaultPDA] = await this.findQuoteVaultPDA(mint);
+
+    return {
+      quoteMint,
+      quoteVault: quoteVaultPDA,
+      traderQuoteAccount: await getAssociatedTokenAddress(quoteMint, trader, true, quoteTokenProgram),
+      creatorQuoteAccount: await getAssociatedTokenAddress(quoteMint, project.creator, true, quoteTokenProgram),
+      feeVaultQuoteAccount: await getAssociatedTokenAddress(quoteMint, feeVaultPDA, true, quoteTokenProgram),
+      quoteTokenProgram,
+    };
+  }
+
+  async findLaunchLedgerPDA(mint: PublicKey): Promise<[PublicKey, number]> {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('launch_ledger'), mint.toBuffer()],
+      this.program.programId
+    );
+  }
+
+  // The cluster that wallets buying inside the sniping window are tagged into
+  async findSniperClusterPDA(mint: PublicKey): Promise<[PublicKey, number]> {
+    return this.findBundleClusterPDA(mint, new anchor.BN('18446744073709551615'));
+  }
+
+  // Resolve the membership and cluster accounts of a wallet, or nulls if it has none
+  async getClusterMembershipAccounts(
+    mint: PublicKey,
+    wallet: PublicKey
+  ): Promise<{ bundleMembership: PublicKey | null; bundleCluster: PublicKey | null }> {
+    const [membershipPDA] = await this.findClusterMembershipPDA(mint, wallet);
+
+    try {
+      const membership = await this.program.account.clusterMembership.fetch(membershipPDA);
+      return { bundleMembership: membershipPDA, bundleCluster: membership.cluster };
+    } catch (error) {
+      return { bundleMembership: null, bundleCluster: null };
+    }
+  }
+
+  // Trades always pass the wallet's derived membership, which the program only loads
+  // once the wallet has joined a cluster
+  async getTradeClusterAccounts(
+    mint: PublicKey,
+    wallet: PublicKey
+  ): Promise<{ bundleMembership: PublicKey; bundleCluster: PublicKey | null }> {
+    const [membershipPDA] = await this.findClusterMembershipPDA(mint, wallet);
+    const { bundleCluster } = await this.getClusterMembershipAccounts(mint, wallet);
+
+    return { bundleMembership: membershipPDA, bundleCluster };
+  }
+
   async initializeLaunchpad(
     feeRecipient: PublicKey,
     bundleThresholdPercentage: number,
@@ -114,27 +387,118 @@ export class LaunchpadClient {
     return tx;
   }
 
+  // Upgrade the config to the current account layout. Run before migrateProject.
+  async migrateConfig(): Promise<string> {
+    const [configPDA] = await this.findConfigPDA();
+
+    const tx = await this.program.methods
+      .migrateConfig()
+      .accounts({
+        config: configPDA,
+        authority: this.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    return tx;
+  }
+
+  // Upgrade a project to the current account layout. Projects priced in an SPL token
+  // pass their quote mint, whose graduation cap they take.
+  async migrateProject(mint: PublicKey, quoteMint: PublicKey | null = null): Promise<string> {
+    const [configPDA] = await this.findConfigPDA();
+    const [projectPDA] = await this.findProjectPDA(mint);
+    const [launchLedgerPDA] = await this.findLaunchLedgerPDA(mint);
+    const [sniperClusterPDA] = await this.findSniperClusterPDA(mint);
+
+    const tx = await this.program.methods
+      .migrateProject()
+      .accounts({
+        config: configPDA,
+        project: projectPDA,
+        mint: mint,
+        quoteConfig: quoteMint ? (await this.findQuoteConfigPDA(quoteMint))[0] : null,
+        launchLedger: launchLedgerPDA,
+        sniperCluster: sniperClusterPDA,
+        authority: this.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    return tx;
+  }
+
   async createTokenProject(
     name: string,
     symbol: string,
     initialPrice: anchor.BN,
-    curveParams: anchor.BN[]
+    curveParams: anchor.BN[],
+    options: CreateTokenProjectOptions = {}
   ): Promise<{ txId: string; mint: PublicKey }> {
     const [configPDA] = await this.findConfigPDA();
     const mintKeypair = Keypair.generate();
     const [projectPDA] = await this.findProjectPDA(mintKeypair.publicKey);
+    const [launchLedgerPDA] = await this.findLaunchLedgerPDA(mintKeypair.publicKey);
+    const [sniperClusterPDA] = await this.findSniperClusterPDA(mintKeypair.publicKey);
+    const [quoteVaultPDA] = await this.findQuoteVaultPDA(mintKeypair.publicKey);
+    const quoteTokenProgram = options.quoteMint
+      ? (await this.connection.getAccountInfo(options.quoteMint))!.owner
+      : null;
+    const [feeVaultPDA] = await this.findFeeVaultPDA();
+    const creatorATA = await getAssociatedTokenAddress(
+      mintKeypair.publicKey,
+      this.wallet.publicKey,
+      true,
+      TOKEN_2022_PROGRAM_ID
+    );
+    const devBuy = options.initialBuyAmount !== undefined;
+    const [vestingEscrowPDA] = await this.findVestingEscrowPDA(mintKeypair.publicKey);
+    const vestingVault = await getAssociatedTokenAddress(
+      mintKeypair.publicKey,
+      vestingEscrowPDA,
+      true,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    const [launchAuctionPDA] = await this.findLaunchAuctionPDA(mintKeypair.publicKey);
 
     const tx = await this.program.methods
       .createTokenProject(
         name,
         symbol,
         initialPrice,
-        curveParams
+        curveParams,
+        options.maxWalletBps ?? null,
+        options.maxBuyPerTx ?? null,
+        options.buyCooldownSecs ?? null,
+        options.uri ?? '',
+        options.extraMetadata ?? [],
+        options.decimals ?? 9,
+        options.initialBuyAmount ?? null,
+        options.maxSolCost ?? null,
+        options.vesting ?? null,
+        options.launchAt ?? null,
+        options.whitelist ?? null,
+        options.launchFee ?? null,
+        options.auctionEndsAt ?? null,
+        options.minRaise ?? null
       )
       .accounts({
         config: configPDA,
         project: projectPDA,
+        launchLedger: launchLedgerPDA,
+        sniperCluster: sniperClusterPDA,
         mint: mintKeypair.publicKey,
+        quoteMint: options.quoteMint ?? null,
+        quoteVault: options.quoteMint ? quoteVaultPDA : null,
+        quoteTokenProgram,
+        quoteConfig: options.quoteMint ? (await this.findQuoteConfigPDA(options.quoteMint))[0] : null,
+        feeVault: devBuy ? feeVaultPDA : null,
+        creatorTokenAccount: devBuy ? creatorATA : null,
+        vestingEscrow: options.vesting ? vestingEscrowPDA : null,
+        vestingVault: options.vesting ? vestingVault : null,
+        associatedTokenProgram: devBuy || options.vesting ? ASSOCIATED_TOKEN_PROGRAM_ID : null,
+        launchAuction: options.auctionEndsAt ? launchAuctionPDA : null,
         authority: this.wallet.publicKey,
         tokenProgram: TOKEN_2022_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
@@ -149,14 +513,110 @@ export class LaunchpadClient {
     };
   }
 
+  // Build the (relationship, token account, bundle tracker) triples that let
+  // the program combine related wallets' holdings into one bundle
+  async getClusterAccounts(
+    mint: PublicKey,
+    wallet: PublicKey,
+    relatedWallets: PublicKey[]
+  ): Promise<anchor.web3.AccountMeta[]> {
+    const accounts: anchor.web3.AccountMeta[] = [];
+
+    for (const relatedWallet of relatedWallets) {
+      // Relationships are keyed by the order they were registered in
+      let [relationshipPDA] = await this.findWalletRelationshipPDA(mint, wallet, relatedWallet);
+      if (!(await this.connection.getAccountInfo(relationshipPDA))) {
+        [relationshipPDA] = await this.findWalletRelationshipPDA(mint, relatedWallet, wallet);
+      }
+
+      const relatedATA = await getAssociatedTokenAddress(
+        mint,
+        relatedWallet,
+        true,
+        TOKEN_2022_PROGRAM_ID
+      );
+      const [relatedTrackerPDA] = await this.findBundleTrackerPDA(mint, relatedWallet);
+      // Passed even when it does not exist, so exempt wallets cannot be hidden
+      const [relatedExemptPDA] = await this.findExemptAddressPDA(relatedWallet);
+
+      accounts.push(
+        { pubkey: relationshipPDA, isSigner: false, isWritable: false },
+        { pubkey: relatedATA, isSigner: false, isWritable: false },
+        { pubkey: relatedTrackerPDA, isSigner: false, isWritable: true },
+        { pubkey: relatedExemptPDA, isSigner: false, isWritable: false },
+      );
+    }
+
+    return accounts;
+  }
+
+  // Write the transfer hook's extra accounts so Token-2022 can resolve them.
+  // Run once per mint after createTokenProject, before any transfer.
+  async initializeExtraAccountMetaList(mint: PublicKey): Promise<string> {
+    const [extraAccountMetaListPDA] = await this.findExtraAccountMetaListPDA(mint);
+    const [projectPDA] = await this.findProjectPDA(mint);
+
+    const tx = await this.program.methods
+      .initializeExtraAccountMetaList()
+      .accounts({
+        extraAccountMetaList: extraAccountMetaListPDA,
+        project: projectPDA,
+        mint,
+        payer: this.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    return tx;
+  }
+
+  // Creator-only until graduation
+  async updateProjectMetadata(
+    mint: PublicKey,
+    update: ProjectMetadataUpdate
+  ): Promise<string> {
+    const [projectPDA] = await this.findProjectPDA(mint);
+
+    const tx = await this.program.methods
+      .updateProjectMetadata(
+        update.name ?? null,
+        update.symbol ?? null,
+        update.uri ?? null,
+        update.extraMetadata ?? []
+      )
+      .accounts({
+        project: projectPDA,
+        mint: mint,
+        creator: this.wallet.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    return tx;
+  }
+
   async buyTokens(
     mint: PublicKey,
-    amount: anchor.BN
+    amount: anchor.BN,
+    relatedWallets: PublicKey[] = [],
+    referrer: PublicKey | null = null,
+    // Merkle proof for the buyer, required during the whitelist phase
+    whitelistProof: number[][] = []
   ): Promise<string> {
     const [configPDA] = await this.findConfigPDA();
     const [projectPDA] = await this.findProjectPDA(mint);
     const [feeVaultPDA] = await this.findFeeVaultPDA();
     const [bundleTrackerPDA] = await this.findBundleTrackerPDA(mint, this.wallet.publicKey);
+    const [whitelistPurchasePDA] = await this.findWhitelistPurchasePDA(mint, this.wallet.publicKey);
+    const [launchLedgerPDA] = await this.findLaunchLedgerPDA(mint);
+    const clusterAccounts = await this.getTradeClusterAccounts(mint, this.wallet.publicKey);
+
+    // Buys inside the sniping window join the sniper cluster
+    if (!clusterAccounts.bundleCluster && (await this.isInSnipeWindow(mint))) {
+      const [sniperClusterPDA] = await this.findSniperClusterPDA(mint);
+      clusterAccounts.bundleCluster = sniperClusterPDA;
+    }
 
     // Get the associated token account for the buyer
     const buyerATA = await getAssociatedTokenAddress(
@@ -183,10 +643,15 @@ export class LaunchpadClient {
       );
     }
 
+    const { traderQuoteAccount, ...quoteAccounts } = await this.getQuoteAccounts(
+      mint,
+      this.wallet.publicKey
+    );
+
     // Add the buy tokens instruction
     tx.add(
       await this.program.methods
-        .buyTokens(amount)
+        .buyTokens(amount, whitelistProof)
         .accounts({
           config: configPDA,
           project: projectPDA,
@@ -195,10 +660,24 @@ export class LaunchpadClient {
           buyerTokenAccount: buyerATA,
           feeVault: feeVaultPDA,
           bundleTracker: bundleTrackerPDA,
+          ...clusterAccounts,
+          launchLedger: launchLedgerPDA,
+          exemptAddress: await this.getExemptAddressAccount(this.wallet.publicKey),
+          feePayer: null,
+          instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+          // Referrals and fee tiers only apply to SOL-priced launches
+          referrer: referrer && !quoteAccounts.quoteMint ? (await this.findReferrerPDA(referrer))[0] : null,
+          traderStats: quoteAccounts.quoteMint ? null : (await this.findTraderStatsPDA(this.wallet.publicKey))[0],
+          whitelistPurchase: whitelistProof.length > 0 ? whitelistPurchasePDA : null,
+          ...quoteAccounts,
+          buyerQuoteAccount: traderQuoteAccount,
           systemProgram: SystemProgram.programId,
           tokenProgram: TOKEN_2022_PROGRAM_ID,
           associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         })
+        .remainingAccounts(
+          await this.getClusterAccounts(mint, this.wallet.publicKey, relatedWallets)
+        )
         .instruction()
     );
 
@@ -215,12 +694,15 @@ export class LaunchpadClient {
 
   async sellTokens(
     mint: PublicKey,
-    amount: anchor.BN
+    amount: anchor.BN,
+    relatedWallets: PublicKey[] = [],
+    referrer: PublicKey | null = null
   ): Promise<string> {
     const [configPDA] = await this.findConfigPDA();
     const [projectPDA] = await this.findProjectPDA(mint);
     const [feeVaultPDA] = await this.findFeeVaultPDA();
     const [bundleTrackerPDA] = await this.findBundleTrackerPDA(mint, this.wallet.publicKey);
+    const clusterAccounts = await this.getTradeClusterAccounts(mint, this.wallet.publicKey);
 
     // Get the associated token account for the seller
     const sellerATA = await getAssociatedTokenAddress(
@@ -230,6 +712,11 @@ export class LaunchpadClient {
       TOKEN_2022_PROGRAM_ID
     );
 
+    const { traderQuoteAccount, ...quoteAccounts } = await this.getQuoteAccounts(
+      mint,
+      this.wallet.publicKey
+    );
+
     // Add the sell tokens instruction
     const tx = await this.program.methods
       .sellTokens(amount)
@@ -241,10 +728,20 @@ export class LaunchpadClient {
         sellerTokenAccount: sellerATA,
         feeVault: feeVaultPDA,
         bundleTracker: bundleTrackerPDA,
+        ...clusterAccounts,
+        exemptAddress: await this.getExemptAddressAccount(this.wallet.publicKey),
+        // Referrals and fee tiers only apply to SOL-priced launches
+        referrer: referrer && !quoteAccounts.quoteMint ? (await this.findReferrerPDA(referrer))[0] : null,
+        traderStats: quoteAccounts.quoteMint ? null : (await this.findTraderStatsPDA(this.wallet.publicKey))[0],
+        ...quoteAccounts,
+        sellerQuoteAccount: traderQuoteAccount,
         systemProgram: SystemProgram.programId,
         tokenProgram: TOKEN_2022_PROGRAM_ID,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
       })
+      .remainingAccounts(
+        await this.getClusterAccounts(mint, this.wallet.publicKey, relatedWallets)
+      )
       .rpc();
 
     return tx;
@@ -286,6 +783,59 @@ export class LaunchpadClient {
     return tx;
   }
 
+  // Permissionlessly derive a relationship between two wallets from the signals
+  // their trades recorded (same-slot buys, shared fee payer, common funding)
+  async inferWalletRelationship(
+    mint: PublicKey,
+    walletA: PublicKey,
+    walletB: PublicKey
+  ): Promise<string> {
+    const [configPDA] = await this.findConfigPDA();
+
+    // Each pair has a single relationship, keyed in ascending wallet order
+    if (Buffer.compare(walletA.toBuffer(), walletB.toBuffer()) > 0) {
+      [walletA, walletB] = [walletB, walletA];
+    }
+
+    const [trackerA] = await this.findBundleTrackerPDA(mint, walletA);
+    const [trackerB] = await this.findBundleTrackerPDA(mint, walletB);
+    const [relationshipPDA] = await this.findWalletRelationshipPDA(mint, walletA, walletB);
+
+    const tx = await this.program.methods
+      .inferWalletRelationship()
+      .accounts({
+        config: configPDA,
+        trackerA: trackerA,
+        trackerB: trackerB,
+        relationship: relationshipPDA,
+        mint: mint,
+        walletA: walletA,
+        walletB: walletB,
+        payer: this.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    return tx;
+  }
+
+  async setRelationshipParams(
+    relationshipThreshold: number,
+    fundingWindowSlots: anchor.BN
+  ): Promise<string> {
+    const [configPDA] = await this.findConfigPDA();
+
+    const tx = await this.program.methods
+      .setRelationshipParams(relationshipThreshold, fundingWindowSlots)
+      .accounts({
+        config: configPDA,
+        authority: this.wallet.publicKey,
+      })
+      .rpc();
+
+    return tx;
+  }
+
   async updateBundleStatus(
     mint: PublicKey,
     wallet: PublicKey,
@@ -309,6 +859,7 @@ export class LaunchpadClient {
         bundleTracker: bundleTrackerPDA,
         mint: mint,
         wallet: wallet,
+        exemptAddress: await this.getExemptAddressAccount(wallet),
         authority: this.wallet.publicKey,
         systemProgram: SystemProgram.programId,
       })
@@ -317,18 +868,684 @@ export class LaunchpadClient {
     return tx;
   }
 
-  async graduateToken(
-    mint: PublicKey,
-    liquidityPool: PublicKey
+  async setBundlePenaltyMode(
+    mode: 'block' | 'confiscate' | 'burn'
   ): Promise<string> {
     const [configPDA] = await this.findConfigPDA();
-    const [projectPDA] = await this.findProjectPDA(mint);
 
     const tx = await this.program.methods
-      .graduateToken(
-        mint,
-        liquidityPool
-      )
+      .setBundlePenaltyMode({ [mode]: {} } as any)
+      .accounts({
+        config: configPDA,
+        authority: this.wallet.publicKey,
+      })
+      .rpc();
+
+    return tx;
+  }
+
+  // Permissionlessly recompute a wallet's bundle from current balances, clearing
+  // the flag if the bundle has fallen under the threshold
+  async reevaluateBundle(
+    mint: PublicKey,
+    wallet: PublicKey,
+    relatedWallets: PublicKey[] = []
+  ): Promise<string> {
+    const [configPDA] = await this.findConfigPDA();
+    const [projectPDA] = await this.findProjectPDA(mint);
+    const [bundleTrackerPDA] = await this.findBundleTrackerPDA(mint, wallet);
+    const clusterAccounts = await this.getTradeClusterAccounts(mint, wallet);
+
+    const walletATA = await getAssociatedTokenAddress(
+      mint,
+      wallet,
+      true,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    const tx = await this.program.methods
+      .reevaluateBundle()
+      .accounts({
+        config: configPDA,
+        project: projectPDA,
+        mint: mint,
+        bundleTracker: bundleTrackerPDA,
+        ...clusterAccounts,
+        wallet: wallet,
+        exemptAddress: await this.getExemptAddressAccount(wallet),
+        walletTokenAccount: walletATA,
+        payer: this.wallet.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .remainingAccounts(await this.getClusterAccounts(mint, wallet, relatedWallets))
+      .rpc();
+
+    return tx;
+  }
+
+  // Clears the wallet's flag and takes it out of its cluster, if it belongs to one
+  async clearBundleFlag(
+    mint: PublicKey,
+    wallet: PublicKey
+  ): Promise<string> {
+    const [configPDA] = await this.findConfigPDA();
+    const [projectPDA] = await this.findProjectPDA(mint);
+    const [bundleTrackerPDA] = await this.findBundleTrackerPDA(mint, wallet);
+    const clusterAccounts = await this.getClusterMembershipAccounts(mint, wallet);
+
+    const tx = await this.program.methods
+      .clearBundleFlag()
+      .accounts({
+        config: configPDA,
+        project: projectPDA,
+        bundleTracker: bundleTrackerPDA,
+        ...clusterAccounts,
+        mint: mint,
+        wallet: wallet,
+        authority: this.wallet.publicKey,
+      })
+      .rpc();
+
+    return tx;
+  }
+
+  async setBundleFlagDuration(
+    bundleFlagDuration: anchor.BN
+  ): Promise<string> {
+    const [configPDA] = await this.findConfigPDA();
+
+    const tx = await this.program.methods
+      .setBundleFlagDuration(bundleFlagDuration)
+      .accounts({
+        config: configPDA,
+        authority: this.wallet.publicKey,
+      })
+      .rpc();
+
+    return tx;
+  }
+
+  async claimVested(mint: PublicKey): Promise<string> {
+    const [projectPDA] = await this.findProjectPDA(mint);
+    const [vestingEscrowPDA] = await this.findVestingEscrowPDA(mint);
+
+    const vestingVault = await getAssociatedTokenAddress(
+      mint,
+      vestingEscrowPDA,
+      true,
+      TOKEN_2022_PROGRAM_ID
+    );
+    const beneficiaryATA = await getAssociatedTokenAddress(
+      mint,
+      this.wallet.publicKey,
+      true,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    const tx = await this.program.methods
+      .claimVested()
+      .accounts({
+        project: projectPDA,
+        mint: mint,
+        vestingEscrow: vestingEscrowPDA,
+        vestingVault: vestingVault,
+        beneficiaryTokenAccount: beneficiaryATA,
+        beneficiary: this.wallet.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    return tx;
+  }
+
+  async commitToLaunch(mint: PublicKey, amount: anchor.BN): Promise<string> {
+    const [launchAuctionPDA] = await this.findLaunchAuctionPDA(mint);
+    const [commitmentPDA] = await this.findAuctionCommitmentPDA(mint, this.wallet.publicKey);
+
+    const tx = await this.program.methods
+      .commitToLaunch(amount)
+      .accounts({
+        mint: mint,
+        launchAuction: launchAuctionPDA,
+        commitment: commitmentPDA,
+        buyer: this.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    return tx;
+  }
+
+  // Take back a commitment in full from an auction nobody settled within a day
+  // of auction_ends_at, or whose launch was cancelled before it settled
+  async withdrawCommitment(mint: PublicKey): Promise<string> {
+    const [projectPDA] = await this.findProjectPDA(mint);
+    const [launchAuctionPDA] = await this.findLaunchAuctionPDA(mint);
+    const [commitmentPDA] = await this.findAuctionCommitmentPDA(mint, this.wallet.publicKey);
+
+    const tx = await this.program.methods
+      .withdrawCommitment()
+      .accounts({
+        project: projectPDA,
+        mint: mint,
+        launchAuction: launchAuctionPDA,
+        commitment: commitmentPDA,
+        wallet: this.wallet.publicKey,
+      })
+      .rpc();
+
+    return tx;
+  }
+
+  // Settle a launch auction and distribute the given wallets' allocations. Anyone
+  // can call this; the caller pays for any token accounts that do not exist yet.
+  async settleLaunch(mint: PublicKey, wallets: PublicKey[] = []): Promise<string> {
+    const [configPDA] = await this.findConfigPDA();
+    const [projectPDA] = await this.findProjectPDA(mint);
+    const [launchAuctionPDA] = await this.findLaunchAuctionPDA(mint);
+    const [feeVaultPDA] = await this.findFeeVaultPDA();
+    const project = await this.program.account.tokenProject.fetch(projectPDA);
+
+    const preInstructions: anchor.web3.TransactionInstruction[] = [];
+    const remainingAccounts: anchor.web3.AccountMeta[] = [];
+    for (const wallet of wallets) {
+      const [commitmentPDA] = await this.findAuctionCommitmentPDA(mint, wallet);
+      const walletATA = await getAssociatedTokenAddress(mint, wallet, true, TOKEN_2022_PROGRAM_ID);
+      preInstructions.push(
+        createAssociatedTokenAccountIdempotentInstruction(
+          this.wallet.publicKey,
+          walletATA,
+          wallet,
+          mint,
+          TOKEN_2022_PROGRAM_ID
+        )
+      );
+      remainingAccounts.push(
+        { pubkey: commitmentPDA, isSigner: false, isWritable: true },
+        { pubkey: wallet, isSigner: false, isWritable: true },
+        { pubkey: walletATA, isSigner: false, isWritable: true },
+      );
+    }
+
+    const tx = await this.program.methods
+      .settleLaunch()
+      .accounts({
+        config: configPDA,
+        project: projectPDA,
+        mint: mint,
+        launchAuction: launchAuctionPDA,
+        creator: project.creator,
+        feeVault: feeVaultPDA,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .remainingAccounts(remainingAccounts)
+      .preInstructions(preInstructions)
+      .rpc();
+
+    return tx;
+  }
+
+  // Cancel a refundable launch: by the creator before anyone else buys, or by
+  // anyone once it has missed its minimum raise
+  async cancelLaunch(mint: PublicKey): Promise<string> {
+    const [projectPDA] = await this.findProjectPDA(mint);
+
+    const tx = await this.program.methods
+      .cancelLaunch()
+      .accounts({
+        project: projectPDA,
+        mint: mint,
+        authority: this.wallet.publicKey,
+      })
+      .rpc();
+
+    return tx;
+  }
+
+  // Burn tokens of a cancelled launch for their share of the reserve
+  async claimRefund(mint: PublicKey, amount: anchor.BN): Promise<string> {
+    const [projectPDA] = await this.findProjectPDA(mint);
+    const holderATA = await getAssociatedTokenAddress(
+      mint,
+      this.wallet.publicKey,
+      true,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    const tx = await this.program.methods
+      .claimRefund(amount)
+      .accounts({
+        project: projectPDA,
+        mint: mint,
+        holderTokenAccount: holderATA,
+        holder: this.wallet.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .rpc();
+
+    return tx;
+  }
+
+  // Buy when the price drops to triggerPrice, or sell when it reaches it
+  async placeOrder(
+    mint: PublicKey,
+    orderId: anchor.BN,
+    side: 'buy' | 'sell',
+    triggerPrice: anchor.BN,
+    amount: anchor.BN,
+    options: LimitOrderOptions
+  ): Promise<string> {
+    const [projectPDA] = await this.findProjectPDA(mint);
+    const [orderPDA] = await this.findOrderPDA(mint, this.wallet.publicKey, orderId);
+    const ownerATA = await getAssociatedTokenAddress(
+      mint,
+      this.wallet.publicKey,
+      true,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    const tx = await this.program.methods
+      .placeOrder(
+        orderId,
+        { [side]: {} } as any,
+        triggerPrice,
+        amount,
+        options.maxSolCost ?? null,
+        options.minProceeds ?? null,
+        options.keeperFee ?? new anchor.BN(0),
+        options.expiresAt
+      )
+      .accounts({
+        project: projectPDA,
+        mint: mint,
+        order: orderPDA,
+        ownerTokenAccount: side === 'sell' ? ownerATA : null,
+        owner: this.wallet.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    return tx;
+  }
+
+  // Owners can cancel any time; anyone can cancel an expired order
+  async cancelOrder(mint: PublicKey, owner: PublicKey, orderId: anchor.BN): Promise<string> {
+    const [projectPDA] = await this.findProjectPDA(mint);
+    const [orderPDA] = await this.findOrderPDA(mint, owner, orderId);
+    const order = await this.program.account.order.fetch(orderPDA);
+    const isSell = 'sell' in order.side;
+    const ownerATA = await getAssociatedTokenAddress(mint, owner, true, TOKEN_2022_PROGRAM_ID);
+
+    const tx = await this.program.methods
+      .cancelOrder()
+      .accounts({
+        project: projectPDA,
+        order: orderPDA,
+        mint: mint,
+        owner: owner,
+        ownerTokenAccount: isSell ? ownerATA : null,
+        authority: this.wallet.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .rpc();
+
+    return tx;
+  }
+
+  // Execute a triggered order as a keeper, earning its keeper fee
+  async executeOrder(
+    mint: PublicKey,
+    owner: PublicKey,
+    orderId: anchor.BN,
+    relatedWallets: PublicKey[] = []
+  ): Promise<string> {
+    const [configPDA] = await this.findConfigPDA();
+    const [projectPDA] = await this.findProjectPDA(mint);
+    const [orderPDA] = await this.findOrderPDA(mint, owner, orderId);
+    const [feeVaultPDA] = await this.findFeeVaultPDA();
+    const project = await this.program.account.tokenProject.fetch(projectPDA);
+    const order = await this.program.account.order.fetch(orderPDA);
+    const isSell = 'sell' in order.side;
+    const ownerATA = await getAssociatedTokenAddress(mint, owner, true, TOKEN_2022_PROGRAM_ID);
+    const [bundleTrackerPDA] = await this.findBundleTrackerPDA(mint, owner);
+    const clusterAccounts = await this.getTradeClusterAccounts(mint, owner);
+
+    const tx = await this.program.methods
+      .executeOrder()
+      .accounts({
+        config: configPDA,
+        project: projectPDA,
+        mint: mint,
+        order: orderPDA,
+        owner: owner,
+        ownerTokenAccount: isSell ? null : ownerATA,
+        creator: project.creator,
+        feeVault: feeVaultPDA,
+        bundleTracker: bundleTrackerPDA,
+        ...clusterAccounts,
+        exemptAddress: await this.getExemptAddressAccount(owner),
+        keeper: this.wallet.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(isSell ? [] : await this.getClusterAccounts(mint, owner, relatedWallets))
+      .rpc();
+
+    return tx;
+  }
+
+  async seizeBundledTokens(
+    mint: PublicKey,
+    wallet: PublicKey
+  ): Promise<string> {
+    const [configPDA] = await this.findConfigPDA();
+    const [projectPDA] = await this.findProjectPDA(mint);
+    const [bundleTrackerPDA] = await this.findBundleTrackerPDA(mint, wallet);
+    const [penaltyVaultPDA] = await this.findPenaltyVaultPDA(mint);
+    const clusterAccounts = await this.getClusterMembershipAccounts(mint, wallet);
+
+    const sourceATA = await getAssociatedTokenAddress(
+      mint,
+      wallet,
+      true,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    const tx = await this.program.methods
+      .seizeBundledTokens()
+      .accounts({
+        config: configPDA,
+        project: projectPDA,
+        mint: mint,
+        bundleTracker: bundleTrackerPDA,
+        ...clusterAccounts,
+        wallet: wallet,
+        exemptAddress: await this.getExemptAddressAccount(wallet),
+        sourceTokenAccount: sourceATA,
+        penaltyVault: penaltyVaultPDA,
+        authority: this.wallet.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    return tx;
+  }
+
+  async createBundleCluster(
+    mint: PublicKey,
+    clusterId: anchor.BN
+  ): Promise<string> {
+    const [configPDA] = await this.findConfigPDA();
+    const [clusterPDA] = await this.findBundleClusterPDA(mint, clusterId);
+
+    const tx = await this.program.methods
+      .createBundleCluster(clusterId)
+      .accounts({
+        config: configPDA,
+        bundleCluster: clusterPDA,
+        mint: mint,
+        authority: this.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    return tx;
+  }
+
+  async addClusterMember(
+    mint: PublicKey,
+    clusterId: anchor.BN,
+    wallet: PublicKey
+  ): Promise<string> {
+    const [configPDA] = await this.findConfigPDA();
+    const [projectPDA] = await this.findProjectPDA(mint);
+    const [clusterPDA] = await this.findBundleClusterPDA(mint, clusterId);
+    const [membershipPDA] = await this.findClusterMembershipPDA(mint, wallet);
+
+    const walletATA = await getAssociatedTokenAddress(
+      mint,
+      wallet,
+      true,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    const tx = await this.program.methods
+      .addClusterMember()
+      .accounts({
+        config: configPDA,
+        project: projectPDA,
+        bundleCluster: clusterPDA,
+        bundleMembership: membershipPDA,
+        mint: mint,
+        wallet: wallet,
+        walletTokenAccount: walletATA,
+        authority: this.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .rpc();
+
+    return tx;
+  }
+
+  async mergeBundleClusters(
+    mint: PublicKey,
+    sourceClusterId: anchor.BN,
+    targetClusterId: anchor.BN,
+    members: PublicKey[]
+  ): Promise<string> {
+    const [configPDA] = await this.findConfigPDA();
+    const [projectPDA] = await this.findProjectPDA(mint);
+    const [sourcePDA] = await this.findBundleClusterPDA(mint, sourceClusterId);
+    const [targetPDA] = await this.findBundleClusterPDA(mint, targetClusterId);
+
+    const tx = await this.program.methods
+      .mergeBundleClusters()
+      .accounts({
+        config: configPDA,
+        project: projectPDA,
+        sourceCluster: sourcePDA,
+        targetCluster: targetPDA,
+        mint: mint,
+        authority: this.wallet.publicKey,
+      })
+      .remainingAccounts(await this.getMembershipMetas(mint, members))
+      .rpc();
+
+    return tx;
+  }
+
+  async splitBundleCluster(
+    mint: PublicKey,
+    sourceClusterId: anchor.BN,
+    newClusterId: anchor.BN,
+    members: PublicKey[]
+  ): Promise<string> {
+    const [configPDA] = await this.findConfigPDA();
+    const [projectPDA] = await this.findProjectPDA(mint);
+    const [sourcePDA] = await this.findBundleClusterPDA(mint, sourceClusterId);
+    const [newClusterPDA] = await this.findBundleClusterPDA(mint, newClusterId);
+
+    const tx = await this.program.methods
+      .splitBundleCluster(newClusterId)
+      .accounts({
+        config: configPDA,
+        project: projectPDA,
+        sourceCluster: sourcePDA,
+        newCluster: newClusterPDA,
+        mint: mint,
+        authority: this.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(await this.getMembershipMetas(mint, members))
+      .rpc();
+
+    return tx;
+  }
+
+  private async getMembershipMetas(
+    mint: PublicKey,
+    members: PublicKey[]
+  ): Promise<anchor.web3.AccountMeta[]> {
+    const metas: anchor.web3.AccountMeta[] = [];
+    for (const member of members) {
+      const [membershipPDA] = await this.findClusterMembershipPDA(mint, member);
+      metas.push({ pubkey: membershipPDA, isSigner: false, isWritable: true });
+    }
+    return metas;
+  }
+
+  // Allowlist an address; scopes is a bitmask of fees (1), bundling (2) and limits (4)
+  async setExemptAddress(
+    address: PublicKey,
+    scopes: number
+  ): Promise<string> {
+    const [configPDA] = await this.findConfigPDA();
+    const [exemptPDA] = await this.findExemptAddressPDA(address);
+
+    const tx = await this.program.methods
+      .setExemptAddress(address, scopes)
+      .accounts({
+        config: configPDA,
+        exemptAddress: exemptPDA,
+        authority: this.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    return tx;
+  }
+
+  async removeExemptAddress(
+    address: PublicKey
+  ): Promise<string> {
+    const [configPDA] = await this.findConfigPDA();
+    const [exemptPDA] = await this.findExemptAddressPDA(address);
+
+    const tx = await this.program.methods
+      .removeExemptAddress(address)
+      .accounts({
+        config: configPDA,
+        exemptAddress: exemptPDA,
+        authority: this.wallet.publicKey,
+      })
+      .rpc();
+
+    return tx;
+  }
+
+  // Reclaim the rent of the wallet's bundle tracker once it has sold out
+  async closeBundleTracker(
+    mint: PublicKey
+  ): Promise<string> {
+    const [bundleTrackerPDA] = await this.findBundleTrackerPDA(mint, this.wallet.publicKey);
+    const [projectPDA] = await this.findProjectPDA(mint);
+    const bundleTracker = await this.program.account.bundleTracker.fetch(bundleTrackerPDA);
+
+    const walletATA = await getAssociatedTokenAddress(
+      mint,
+      this.wallet.publicKey,
+      true,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    const tx = await this.program.methods
+      .closeBundleTracker()
+      .accounts({
+        bundleTracker: bundleTrackerPDA,
+        project: projectPDA,
+        mint: mint,
+        wallet: this.wallet.publicKey,
+        rentPayer: bundleTracker.rentPayer,
+        walletTokenAccount: walletATA,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .rpc();
+
+    return tx;
+  }
+
+  async closeRelationship(
+    mint: PublicKey,
+    walletA: PublicKey,
+    walletB: PublicKey
+  ): Promise<string> {
+    const [configPDA] = await this.findConfigPDA();
+    const [relationshipPDA] = await this.findWalletRelationshipPDA(mint, walletA, walletB);
+    const relationship = await this.program.account.walletRelationship.fetch(relationshipPDA);
+
+    const tx = await this.program.methods
+      .closeRelationship()
+      .accounts({
+        config: configPDA,
+        relationship: relationshipPDA,
+        rentPayer: relationship.rentPayer,
+        authority: this.wallet.publicKey,
+      })
+      .rpc();
+
+    return tx;
+  }
+
+  // Bulk-close the bundle trackers and relationships of a graduated project.
+  // Each account's rent goes back to whoever paid for it.
+  async closeGraduatedAccounts(
+    mint: PublicKey,
+    wallets: PublicKey[],
+    relationships: PublicKey[] = []
+  ): Promise<string> {
+    const [configPDA] = await this.findConfigPDA();
+    const [projectPDA] = await this.findProjectPDA(mint);
+
+    const remainingAccounts: anchor.web3.AccountMeta[] = [];
+    for (const wallet of wallets) {
+      const [bundleTrackerPDA] = await this.findBundleTrackerPDA(mint, wallet);
+      const bundleTracker = await this.program.account.bundleTracker.fetch(bundleTrackerPDA);
+      remainingAccounts.push(
+        { pubkey: bundleTrackerPDA, isSigner: false, isWritable: true },
+        { pubkey: bundleTracker.rentPayer, isSigner: false, isWritable: true },
+      );
+    }
+    for (const relationship of relationships) {
+      const { rentPayer } = await this.program.account.walletRelationship.fetch(relationship);
+      remainingAccounts.push(
+        { pubkey: relationship, isSigner: false, isWritable: true },
+        { pubkey: rentPayer, isSigner: false, isWritable: true },
+      );
+    }
+
+    const tx = await this.program.methods
+      .closeGraduatedAccounts()
+      .accounts({
+        config: configPDA,
+        project: projectPDA,
+        mint: mint,
+        authority: this.wallet.publicKey,
+      })
+      .remainingAccounts(remainingAccounts)
+      .rpc();
+
+    return tx;
+  }
+
+  async graduateToken(
+    mint: PublicKey,
+    liquidityPool: PublicKey
+  ): Promise<string> {
+    const [configPDA] = await this.findConfigPDA();
+    const [projectPDA] = await this.findProjectPDA(mint);
+
+    const tx = await this.program.methods
+      .graduateToken(
+        mint,
+        liquidityPool
+      )
       .accounts({
         config: configPDA,
         project: projectPDA,
@@ -345,11 +1562,19 @@ export class LaunchpadClient {
   async createRaydiumPool(
     mint: PublicKey,
     initialLiquidityAmount: anchor.BN,
-    initialTokenAmount: anchor.BN
+    initialTokenAmount: anchor.BN,
+    poolReserveAccount: PublicKey
   ): Promise<string> {
     const [configPDA] = await this.findConfigPDA();
     const [projectPDA] = await this.findProjectPDA(mint);
     const liquidityPoolKeypair = Keypair.generate();
+    const { quoteMint, quoteVault, quoteTokenProgram } = await this.getQuoteAccounts(mint, this.wallet.publicKey);
+
+    // SOL reserves are wrapped into the project's WSOL account before they move to the pool,
+    // quote-token reserves move straight from the quote vault
+    const projectWsolAccount = quoteMint
+      ? null
+      : await getAssociatedTokenAddress(NATIVE_MINT, projectPDA, true);
 
     const tx = await this.program.methods
       .createRaydiumPool(
@@ -363,8 +1588,16 @@ export class LaunchpadClient {
         mint: mint,
         authority: this.wallet.publicKey,
         liquidityPool: liquidityPoolKeypair.publicKey,
+        projectWsolAccount,
+        poolWsolAccount: quoteMint ? null : poolReserveAccount,
+        wsolMint: quoteMint ? null : NATIVE_MINT,
+        quoteMint,
+        quoteVault,
+        poolQuoteAccount: quoteMint ? poolReserveAccount : null,
+        quoteTokenProgram,
         systemProgram: SystemProgram.programId,
-        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         rent: SYSVAR_RENT_PUBKEY,
       })
       .signers([liquidityPoolKeypair])
@@ -373,6 +1606,85 @@ export class LaunchpadClient {
     return tx;
   }
 
+  async setReferralFee(referralFeeBps: number): Promise<string> {
+    const [configPDA] = await this.findConfigPDA();
+
+    const tx = await this.program.methods
+      .setReferralFee(referralFeeBps)
+      .accounts({
+        config: configPDA,
+        authority: this.wallet.publicKey,
+      })
+      .rpc();
+
+    return tx;
+  }
+
+  // Graduation market cap for projects priced in quoteMint, in its base units
+  async setQuoteGraduationCap(quoteMint: PublicKey, graduationMarketCap: anchor.BN): Promise<string> {
+    const [configPDA] = await this.findConfigPDA();
+    const [quoteConfigPDA] = await this.findQuoteConfigPDA(quoteMint);
+
+    const tx = await this.program.methods
+      .setQuoteGraduationCap(graduationMarketCap)
+      .accounts({
+        config: configPDA,
+        quoteConfig: quoteConfigPDA,
+        quoteMint,
+        authority: this.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    return tx;
+  }
+
+  // Tiers must be ordered by ascending minVolume (lamports) and descending feeBps
+  async setFeeTiers(
+    feeTiers: { minVolume: anchor.BN; feeBps: number }[]
+  ): Promise<string> {
+    const [configPDA] = await this.findConfigPDA();
+
+    const tx = await this.program.methods
+      .setFeeTiers(feeTiers)
+      .accounts({
+        config: configPDA,
+        authority: this.wallet.publicKey,
+      })
+      .rpc();
+
+    return tx;
+  }
+
+  async registerReferrer(): Promise<string> {
+    const [referrerPDA] = await this.findReferrerPDA(this.wallet.publicKey);
+
+    const tx = await this.program.methods
+      .registerReferrer()
+      .accounts({
+        referrer: referrerPDA,
+        wallet: this.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    return tx;
+  }
+
+  async claimReferralFees(): Promise<string> {
+    const [referrerPDA] = await this.findReferrerPDA(this.wallet.publicKey);
+
+    const tx = await this.program.methods
+      .claimReferralFees()
+      .accounts({
+        referrer: referrerPDA,
+        wallet: this.wallet.publicKey,
+      })
+      .rpc();
+
+    return tx;
+  }
+
   async withdrawPlatformFees(
     amount: anchor.BN,
     recipient: PublicKey
@@ -394,6 +1706,37 @@ export class LaunchpadClient {
     return tx;
   }
 
+  // Helper method to check if a buy now would land in the sniping window
+  async isInSnipeWindow(mint: PublicKey): Promise<boolean> {
+    const [configPDA] = await this.findConfigPDA();
+    const [projectPDA] = await this.findProjectPDA(mint);
+
+    const config = await this.program.account.launchpadConfig.fetch(configPDA);
+    const project = await this.program.account.tokenProject.fetch(projectPDA);
+    const slot = await this.connection.getSlot('confirmed');
+
+    const elapsed = slot - new anchor.BN(project.launchSlot).toNumber();
+    return elapsed >= 0 && elapsed < new anchor.BN(config.snipeWindowSlots).toNumber();
+  }
+
+  async setSnipeRules(
+    snipeWindowSlots: anchor.BN,
+    snipeTaxBps: number,
+    snipeBlock: boolean
+  ): Promise<string> {
+    const [configPDA] = await this.findConfigPDA();
+
+    const tx = await this.program.methods
+      .setSnipeRules(snipeWindowSlots, snipeTaxBps, snipeBlock)
+      .accounts({
+        config: configPDA,
+        authority: this.wallet.publicKey,
+      })
+      .rpc();
+
+    return tx;
+  }
+
   // Helper method to check if a wallet is bundling
   async checkBundlingStatus(
     mint: PublicKey,
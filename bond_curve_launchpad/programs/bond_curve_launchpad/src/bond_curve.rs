@@ -1,5 +1,11 @@
 use anchor_lang::prelude::*;
 
+use crate::ErrorCode;
+use crate::vesting::MAX_PREMINT_BPS;
+
+/// Decimals of native SOL. The graduation market cap is expressed at this precision.
+pub const SOL_DECIMALS: u8 = 9;
+
 /// Calculate the price to buy a specific amount of tokens based on the current supply
 pub fn calculate_buy_price(
     curve_params: &[u64],
@@ -12,38 +18,12 @@ pub fn calculate_buy_price(
 
     let base = curve_params[0];
     let initial_price = curve_params[1];
-
-    // For exponential curve, we need to integrate the price function
-    // price = initial_price * (base/10000)^supply
-    // We'll use a numerical approximation by summing small increments
-    
-    let mut total_cost: u64 = 0;
-    let increment_size: u64 = 1; // Can be adjusted for precision vs performance
     
-    let mut remaining = amount;
-    let mut current = current_supply;
+    // Buys round up so the reserve always covers the matching sell
+    let cost = curve_integral(base, initial_price, current_supply, amount, true)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
     
-    while remaining > 0 {
-        let increment = if remaining > increment_size { increment_size } else { remaining };
-        
-        // Calculate price at current supply
-        let price_at_current = calculate_price_at_supply(initial_price, base, current)?;
-        
-        // Add the cost for this increment
-        let increment_cost = price_at_current.checked_mul(increment)
-            .ok_or(error!(ErrorCode::MathOverflow))?;
-            
-        total_cost = total_cost.checked_add(increment_cost)
-            .ok_or(error!(ErrorCode::MathOverflow))?;
-        
-        // Update remaining and current
-        remaining = remaining.checked_sub(increment)
-            .ok_or(error!(ErrorCode::MathOverflow))?;
-        current = current.checked_add(increment)
-            .ok_or(error!(ErrorCode::MathOverflow))?;
-    }
-    
-    Ok(total_cost)
+    u64::try_from(cost).map_err(|_| error!(ErrorCode::MathOverflow))
 }
 
 /// Calculate the price to sell a specific amount of tokens based on the current supply
@@ -63,41 +43,118 @@ pub fn calculate_sell_price(
     let base = curve_params[0];
     let initial_price = curve_params[1];
 
-    // For selling, we integrate from (current_supply - amount) to current_supply
-    // We'll use a numerical approximation by summing small decrements
+    // Selling walks back down the same stretch of the curve the tokens were bought on
+    let proceeds = curve_integral(base, initial_price, current_supply - amount, amount, false)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+    
+    u64::try_from(proceeds).map_err(|_| error!(ErrorCode::MathOverflow))
+}
+
+/// Calculate how many tokens `budget` buys from the curve at the current supply,
+/// and what they cost. Searches over `calculate_buy_price`, so buying the returned
+/// amount costs exactly the returned cost.
+pub fn calculate_tokens_for_cost(
+    curve_params: &[u64],
+    current_supply: u64,
+    budget: u64,
+) -> Result<(u64, u64)> {
+    if curve_params.len() < 2 {
+        return Err(error!(ErrorCode::InvalidCurveParams));
+    }
+
+    let base = curve_params[0];
+    let initial_price = curve_params[1];
     
-    let mut total_return: u64 = 0;
-    let decrement_size: u64 = 1; // Can be adjusted for precision vs performance
+    let affordable = |amount: u64| {
+        matches!(
+            curve_integral(base, initial_price, current_supply, amount, true),
+            Some(cost) if cost <= budget as u128
+        )
+    };
     
-    let mut remaining = amount;
-    let mut current = current_supply;
+    // Double an upper bound until the budget no longer covers it. Curves whose
+    // price falls to zero would make the amount unbounded.
+    let mut high: u64 = 1;
+    while affordable(high) {
+        high = high.checked_mul(2).ok_or(error!(ErrorCode::InvalidCurveParams))?;
+    }
     
-    while remaining > 0 {
-        let decrement = if remaining > decrement_size { decrement_size } else { remaining };
-        
-        // Calculate price at current supply - decrement
-        current = current.checked_sub(decrement)
-            .ok_or(error!(ErrorCode::MathOverflow))?;
-            
-        // Calculate price at updated current supply
-        let price_at_current = calculate_price_at_supply(initial_price, base, current)?;
+    // Nothing is always affordable, so the answer lies in [low, high)
+    let mut low: u64 = 0;
+    while high - low > 1 {
+        let mid = low + (high - low) / 2;
+        if affordable(mid) {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    
+    let cost = calculate_buy_price(curve_params, current_supply, low)?;
+    Ok((low, cost))
+}
+
+/// Fixed-point scale for powers of the curve's growth rate
+const CURVE_SCALE: u128 = 1_000_000_000_000;
+
+/// Sum of the prices of `amount` tokens starting at `start_supply`, or `None` on
+/// overflow. Prices grow geometrically by r = base / 10000 per token, so the sum is
+/// initial_price * (r^end - r^start) / (r - 1) and needs no walk along the curve.
+fn curve_integral(base: u64, initial_price: u64, start_supply: u64, amount: u64, round_up: bool) -> Option<u128> {
+    // A flat curve has no growth rate to divide by
+    if base == 10000 {
+        return (initial_price as u128).checked_mul(amount as u128);
+    }
+    
+    let end_supply = start_supply.checked_add(amount)?;
+    let start_growth = curve_growth(base, start_supply)?;
+    let end_growth = curve_growth(base, end_supply)?;
+    
+    // Falling curves (base below 10000) flip the sign of both differences
+    let growth = if base > 10000 {
+        end_growth.checked_sub(start_growth)?
+    } else {
+        start_growth.checked_sub(end_growth)?
+    };
+    
+    let numerator = (initial_price as u128)
+        .checked_mul(growth)?
+        .checked_mul(10000)?;
+    let denominator = (base.abs_diff(10000) as u128).checked_mul(CURVE_SCALE)?;
+    
+    let sum = numerator / denominator;
+    if round_up && numerator % denominator != 0 {
+        sum.checked_add(1)
+    } else {
+        Some(sum)
+    }
+}
+
+/// (base / 10000)^exponent at `CURVE_SCALE` precision, or `None` on overflow
+fn curve_growth(base: u64, exponent: u64) -> Option<u128> {
+    let mut result = CURVE_SCALE;
+    let mut factor = (base as u128).checked_mul(CURVE_SCALE)? / 10000;
+    let mut exp = exponent;
+    
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result.checked_mul(factor)? / CURVE_SCALE;
+        }
         
-        // Add the return for this decrement
-        let decrement_return = price_at_current.checked_mul(decrement)
-            .ok_or(error!(ErrorCode::MathOverflow))?;
-            
-        total_return = total_return.checked_add(decrement_return)
-            .ok_or(error!(ErrorCode::MathOverflow))?;
+        exp >>= 1;
         
-        // Update remaining
-        remaining = remaining.checked_sub(decrement)
-            .ok_or(error!(ErrorCode::MathOverflow))?;
+        // Only square when a higher bit still needs it, so the last step cannot overflow
+        if exp > 0 {
+            factor = factor.checked_mul(factor)? / CURVE_SCALE;
+        }
     }
     
-    Ok(total_return)
+    Some(result)
 }
 
-/// Calculate the current price based on the current supply
+/// Calculate the current price based on the current supply. This is the price of
+/// the next token, initial_price * r^supply, from the same growth function trades
+/// are priced with.
 pub fn calculate_current_price(
     curve_params: &[u64],
     supply: u64,
@@ -109,75 +166,190 @@ pub fn calculate_current_price(
     let base = curve_params[0];
     let initial_price = curve_params[1];
     
-    calculate_price_at_supply(initial_price, base, supply)
+    let growth = curve_growth(base, supply).ok_or(error!(ErrorCode::MathOverflow))?;
+    let price = (initial_price as u128)
+        .checked_mul(growth)
+        .ok_or(error!(ErrorCode::MathOverflow))?
+        / CURVE_SCALE;
+    
+    u64::try_from(price).map_err(|_| error!(ErrorCode::MathOverflow))
 }
 
-/// Helper function to calculate price at a specific supply point
-pub fn calculate_price_at_supply(initial_price: u64, base: u64, supply: u64) -> Result<u64> {
-    // Price = initial_price * (base/10000)^supply
-    // We'll use repeated multiplication for simplicity and to avoid floating point
-    
-    // For base = 10000 (1.0), price is constant
-    if base == 10000 {
-        return Ok(initial_price);
-    }
-    
-    // For supply = 0, price is initial_price
-    if supply == 0 {
-        return Ok(initial_price);
-    }
-    
-    // For large supplies, we need to be careful about overflow
-    // We'll use a more efficient algorithm for large supplies
-    if supply > 100 {
-        // Use binary exponentiation for large powers
-        let mut result = initial_price;
-        let mut base_power = base;
-        let mut exp = supply;
-        
-        while exp > 0 {
-            if exp & 1 == 1 {
-                result = result.checked_mul(base_power)
+/// Calculate the market cap based on current supply and price
+pub fn calculate_market_cap(supply: u64, price: u64) -> Result<u64> {
+    supply.checked_mul(price)
+        .ok_or(error!(ErrorCode::MathOverflow))
+}
+
+/// Convert an amount expressed in billionths of a token, the precision of lamports,
+/// into base units of a token with `quote_decimals`. Only the decimals change: a SOL
+/// figure does not become the same value in another quote.
+pub fn scale_to_quote_decimals(amount: u64, quote_decimals: u8) -> Result<u64> {
+    let scaled = if quote_decimals >= SOL_DECIMALS {
+        (amount as u128)
+            .checked_mul(
+                10u128
+                    .checked_pow((quote_decimals - SOL_DECIMALS) as u32)
                     .ok_or(error!(ErrorCode::MathOverflow))?
-                    .checked_div(10000)
-                    .ok_or(error!(ErrorCode::DivisionByZero))?;
-            }
-            
-            base_power = base_power.checked_mul(base_power)
-                .ok_or(error!(ErrorCode::MathOverflow))?
-                .checked_div(10000)
-                .ok_or(error!(ErrorCode::DivisionByZero))?;
-                
-            exp >>= 1;
-        }
-        
-        return Ok(result);
+            )
+            .ok_or(error!(ErrorCode::MathOverflow))?
+    } else {
+        (amount as u128) / 10u128.pow((SOL_DECIMALS - quote_decimals) as u32)
+    };
+    
+    u64::try_from(scaled).map_err(|_| error!(ErrorCode::MathOverflow))
+}
+
+/// Express a curve given in billionths of the quote token in its base units, so curves
+/// are written at one precision whatever the quote's decimals. A starting price too
+/// small to survive the scaling is rejected rather than rounded down to free.
+pub fn normalize_curve_params(mut curve_params: Vec<u64>, quote_decimals: u8) -> Result<Vec<u64>> {
+    if curve_params.len() < 2 {
+        return Err(error!(ErrorCode::InvalidCurveParams));
     }
     
-    // For small supplies, direct calculation is fine
-    let mut result = initial_price;
-    for _ in 0..supply {
-        result = result.checked_mul(base)
-            .ok_or(error!(ErrorCode::MathOverflow))?
-            .checked_div(10000)
-            .ok_or(error!(ErrorCode::DivisionByZero))?;
+    let initial_price = scale_to_quote_decimals(curve_params[1], quote_decimals)?;
+    if initial_price == 0 && curve_params[1] != 0 {
+        return Err(error!(ErrorCode::InvalidCurveParams));
     }
     
-    Ok(result)
+    curve_params[1] = initial_price;
+    Ok(curve_params)
 }
 
-/// Calculate the market cap based on current supply and price
-pub fn calculate_market_cap(supply: u64, price: u64) -> Result<u64> {
-    supply.checked_mul(price)
+/// Total supply of a token: what was sold through the curve plus tokens minted
+/// outside it, such as a vesting creator allocation. The curve prices only the
+/// former, but market cap is measured on the total.
+pub fn calculate_total_supply(curve_supply: u64, premint_supply: u64) -> Result<u64> {
+    curve_supply.checked_add(premint_supply)
         .ok_or(error!(ErrorCode::MathOverflow))
 }
 
+/// Premint supply counted towards graduation. The allocation never paid into the
+/// reserve, so it counts for at most `MAX_PREMINT_BPS` of the supply measured and a
+/// project cannot graduate on its premint alone.
+pub fn counted_premint_supply(curve_supply: u64, premint_supply: u64) -> Result<u64> {
+    let max_counted = (curve_supply as u128)
+        .checked_mul(MAX_PREMINT_BPS as u128)
+        .ok_or(error!(ErrorCode::MathOverflow))?
+        .checked_div(10000 - MAX_PREMINT_BPS as u128)
+        .ok_or(error!(ErrorCode::DivisionByZero))?;
+    
+    Ok((premint_supply as u128).min(max_counted) as u64)
+}
+
 /// Check if a token is eligible for graduation
 pub fn is_eligible_for_graduation(
     supply: u64,
+    premint_supply: u64,
     current_price: u64,
     graduation_market_cap: u64
 ) -> Result<bool> {
-    let market_cap = calculate_market_cap(supply, current_price)?;
-    Ok(market_cap >= graduation_market_cap)
-}
\ No newline at end of file
+    // Prices and the cap are both in base units of the project's quote. Measured in
+    // u128, since a large supply at a high price overflows u64 well before graduation
+    // is checked.
+    let total_supply = calculate_total_supply(supply, counted_premint_supply(supply, premint_supply)?)?;
+    let market_cap = (total_supply as u128)
+        .checked_mul(current_price as u128)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+    Ok(market_cap >= graduation_market_cap as u128)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokens_for_cost_buys_as_much_as_the_budget_allows() {
+        // 1% dearer per token
+        let curve_params = [10100, 1_000_000];
+        
+        for (supply, budget) in [(0, 50_000_000), (100, 123_456_789), (0, 999_999)] {
+            let (tokens, cost) = calculate_tokens_for_cost(&curve_params, supply, budget).unwrap();
+            assert_eq!(cost, calculate_buy_price(&curve_params, supply, tokens).unwrap());
+            assert!(cost <= budget);
+            assert!(calculate_buy_price(&curve_params, supply, tokens + 1).unwrap() > budget);
+        }
+    }
+
+    #[test]
+    fn tokens_for_cost_on_a_flat_curve() {
+        assert_eq!(calculate_tokens_for_cost(&[10000, 1000], 0, 9_900).unwrap(), (9, 9_000));
+        assert!(calculate_tokens_for_cost(&[10000, 0], 0, 9_900).is_err());
+    }
+
+    #[test]
+    fn current_price_is_the_marginal_cost_of_the_next_token() {
+        let cases = [
+            (10100, 1_000_000, [0, 1, 100, 2047, 2048, 2500]),
+            (9900, 1_000_000, [0, 1, 100, 500, 2048, 5000]),
+            (10001, 1_000_000_000, [0, 100, 2048, 4000, 10_000, 100_000]),
+        ];
+        
+        for (base, initial_price, supplies) in cases {
+            for supply in supplies {
+                let price = calculate_current_price(&[base, initial_price], supply).unwrap();
+                let marginal = curve_integral(base, initial_price, supply, 1, false).unwrap() as u64;
+                
+                // Both round through the same fixed-point powers, so they agree to a millionth
+                assert!(price.abs_diff(marginal) <= price / 1_000_000, "base {} supply {}", base, supply);
+            }
+        }
+        
+        assert_eq!(calculate_current_price(&[10100, 1_000_000], 0).unwrap(), 1_000_000);
+        assert_eq!(calculate_current_price(&[10000, 1_000], 1_000_000).unwrap(), 1_000);
+        assert!(calculate_current_price(&[10100, 1_000_000], 5000).is_err());
+    }
+
+    #[test]
+    fn selling_never_returns_more_than_buying_cost() {
+        let curve_params = [10100, 1_000_000];
+        
+        let bought = calculate_buy_price(&curve_params, 500, 250).unwrap();
+        let sold = calculate_sell_price(&curve_params, 750, 250).unwrap();
+        assert!(sold <= bought);
+        assert!(bought - sold <= 1);
+        
+        // Buying in two steps costs at least as much as buying at once
+        let split = calculate_buy_price(&curve_params, 500, 100).unwrap()
+            + calculate_buy_price(&curve_params, 600, 150).unwrap();
+        assert!(split >= bought);
+    }
+
+    #[test]
+    fn premint_alone_never_graduates() {
+        // 1 SOL cap at 1,000 lamports per token is a million tokens
+        assert!(!is_eligible_for_graduation(0, 10_000_000, 1_000, 1_000_000_000).unwrap());
+        
+        // The premint counts for at most a fifth of the supply measured
+        assert_eq!(counted_premint_supply(800_000, 10_000_000).unwrap(), 200_000);
+        assert!(is_eligible_for_graduation(800_000, 10_000_000, 1_000, 1_000_000_000).unwrap());
+        assert!(!is_eligible_for_graduation(799_999, 10_000_000, 1_000, 1_000_000_000).unwrap());
+    }
+
+    #[test]
+    fn curves_are_written_at_one_precision_in_any_quote() {
+        // 1,000 billionths of a USDC is one base unit at 6 decimals
+        assert_eq!(normalize_curve_params(vec![10100, 1_000], 6).unwrap(), vec![10100, 1]);
+        assert_eq!(normalize_curve_params(vec![10100, 1_000], SOL_DECIMALS).unwrap(), vec![10100, 1_000]);
+        
+        // A 69,000 USDC cap at 0.001 USDC per token graduates at 69 million tokens,
+        // not at the 69 USDC a 69 SOL cap shifted by decimals would give
+        let usdc_curve = normalize_curve_params(vec![10000, 1_000_000], 6).unwrap();
+        let usdc_cap = 69_000 * 10u64.pow(6);
+        assert!(is_eligible_for_graduation(69_000_000, 0, usdc_curve[1], usdc_cap).unwrap());
+        assert!(!is_eligible_for_graduation(68_999_999, 0, usdc_curve[1], usdc_cap).unwrap());
+        assert!(!is_eligible_for_graduation(69_000, 0, usdc_curve[1], usdc_cap).unwrap());
+    }
+
+    #[test]
+    fn rejects_prices_lost_to_scaling() {
+        assert!(normalize_curve_params(vec![10100, 999], 6).is_err());
+        assert!(normalize_curve_params(vec![10100], SOL_DECIMALS).is_err());
+    }
+
+    #[test]
+    fn market_cap_beyond_u64_is_still_eligible() {
+        assert!(is_eligible_for_graduation(u64::MAX / 2, u64::MAX / 8, u64::MAX, u64::MAX).unwrap());
+    }
+}
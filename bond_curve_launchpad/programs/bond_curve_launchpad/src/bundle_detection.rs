@@ -1,4 +1,34 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
+use anchor_lang::system_program;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::{BundleCluster, BundleFlagReason, BundleTracker, ClusterMembership, ErrorCode, ExemptAddress, TokenProject, WalletRelationship};
+use crate::exemptions::{has_exemption, load_exemption, EXEMPT_BUNDLING};
+use crate::sniper_detection::SNIPER_CLUSTER_ID;
+use crate::vesting::vesting_escrow_address;
+
+/// Maximum number of related wallets a bundle tracker can hold
+pub const MAX_RELATED_WALLETS: usize = 20;
+
+/// Relationship strength added when two wallets bought in the same slot (in basis points)
+pub const SAME_SLOT_WEIGHT: u16 = 2000;
+
+/// Relationship strength added when two wallets share a fee payer (in basis points)
+pub const SHARED_FEE_PAYER_WEIGHT: u16 = 3000;
+
+/// Relationship strength added when two wallets were funded by the same source (in basis points)
+pub const COMMON_FUNDING_WEIGHT: u16 = 5000;
+
+/// Maximum relationship strength (in basis points)
+pub const MAX_RELATIONSHIP_STRENGTH: u16 = 10000;
+
+/// Seconds after which an unrefreshed relationship loses half its strength
+pub const RELATIONSHIP_HALF_LIFE: i64 = 7 * 24 * 60 * 60;
+
+/// Remaining accounts passed per related wallet: relationship, token account, bundle
+/// tracker and exempt address PDA
+pub const RELATED_WALLET_ACCOUNTS: usize = 4;
 
 /// Calculate the percentage of total supply held by a bundle (in basis points)
 pub fn calculate_bundle_percentage(bundle_balance: u64, total_supply: u64) -> Result<u16> {
@@ -21,24 +51,67 @@ pub fn calculate_bundle_percentage(bundle_balance: u64, total_supply: u64) -> Re
     Ok(percentage as u16)
 }
 
-/// Check if a wallet is bundling based on the bundle tracker data
+/// Balance of a wallet that counts toward bundling. The creator's dev-buy is a
+/// disclosed allocation, so it is left out of the creator's holdings.
+pub fn bundle_balance(project: &TokenProject, wallet: &Pubkey, balance: u64) -> u64 {
+    if *wallet == project.creator {
+        balance.saturating_sub(project.creator_allocation)
+    } else {
+        balance
+    }
+}
+
+/// Shrink the creator's disclosed allocation to what the creator still holds. Tokens
+/// that leave the creator's wallet stop being exempt, even if they are bought back.
+pub fn cap_creator_allocation(project: &mut TokenProject, wallet: &Pubkey, balance: u64) {
+    if *wallet == project.creator {
+        project.creator_allocation = project.creator_allocation.min(balance);
+    }
+}
+
+/// Check whether a tracker's bundling flag has passed its expiry
+pub fn is_flag_expired(bundle_tracker: &BundleTracker, now: i64) -> bool {
+    bundle_tracker.flag_expires_at != 0 && now >= bundle_tracker.flag_expires_at
+}
+
+/// Check if a wallet is bundling based on the bundle tracker data and, when the
+/// wallet belongs to one, the aggregate of its bundle cluster
 pub fn is_bundling(
     bundle_tracker: &Account<BundleTracker>,
+    bundle_cluster: Option<&Account<BundleCluster>>,
     threshold_percentage: u16,
-    total_supply: u64
+    total_supply: u64,
+    exemption: Option<&ExemptAddress>
 ) -> Result<bool> {
-    // If the bundle tracker already indicates bundling, return that
-    if bundle_tracker.is_bundling {
+    // Allowlisted addresses such as program vaults and pools are never bundling
+    if has_exemption(exemption, &bundle_tracker.wallet, EXEMPT_BUNDLING) {
+        return Ok(false);
+    }
+    
+    // If the bundle tracker already indicates bundling, return that until the flag expires
+    if bundle_tracker.is_bundling && !is_flag_expired(bundle_tracker, Clock::get()?.unix_timestamp) {
         return Ok(true);
     }
     
+    // A cluster is judged as a whole, so its flag applies to every member
+    if let Some(cluster) = bundle_cluster {
+        if cluster.is_bundling {
+            return Ok(true);
+        }
+    }
+    
     // Calculate the percentage of total supply held by this bundle
     if total_supply == 0 {
         return Ok(false); // Can't be bundling if there's no supply
     }
     
+    let bundle_balance = match bundle_cluster {
+        Some(cluster) => cluster.total_balance.max(bundle_tracker.total_bundle_balance),
+        None => bundle_tracker.total_bundle_balance,
+    };
+    
     let bundle_percentage = calculate_bundle_percentage(
-        bundle_tracker.total_bundle_balance,
+        bundle_balance,
         total_supply
     )?;
     
@@ -54,14 +127,24 @@ pub fn update_bundle_tracker(
     related_wallets: Vec<Pubkey>,
     total_bundle_balance: u64,
     threshold_percentage: u16,
-    total_supply: u64
+    total_supply: u64,
+    flag_reason: BundleFlagReason,
+    flag_duration: i64,
+    exemption: Option<&ExemptAddress>
 ) -> Result<()> {
+    // The tracker account is sized for at most MAX_RELATED_WALLETS entries
+    if related_wallets.len() > MAX_RELATED_WALLETS {
+        return Err(error!(ErrorCode::TooManyRelatedWallets));
+    }
+    
+    let now = Clock::get()?.unix_timestamp;
+    
     // Update the basic information
     bundle_tracker.wallet = *wallet;
     bundle_tracker.mint = *mint;
     bundle_tracker.related_wallets = related_wallets;
     bundle_tracker.total_bundle_balance = total_bundle_balance;
-    bundle_tracker.last_updated = Clock::get()?.unix_timestamp;
+    bundle_tracker.last_updated = now;
     
     // Calculate if this bundle exceeds the threshold
     let bundle_percentage = calculate_bundle_percentage(
@@ -69,17 +152,589 @@ pub fn update_bundle_tracker(
         total_supply
     )?;
     
+    // Allowlisted addresses are tracked but never flagged
+    if has_exemption(exemption, wallet, EXEMPT_BUNDLING) {
+        clear_bundle_flag(bundle_tracker);
+        return Ok(());
+    }
+    
+    // Manual flags stand until they expire or the authority clears them
+    let manual_flag_active = bundle_tracker.is_bundling
+        && bundle_tracker.flag_reason == BundleFlagReason::Manual
+        && !is_flag_expired(bundle_tracker, now);
+    
     // Update the bundling status
-    bundle_tracker.is_bundling = bundle_percentage > threshold_percentage;
+    if manual_flag_active {
+        return Ok(());
+    }
+    
+    if bundle_percentage > threshold_percentage {
+        // Each time the threshold is crossed the flag's expiry is pushed back
+        bundle_tracker.is_bundling = true;
+        bundle_tracker.flag_reason = flag_reason;
+        bundle_tracker.flag_expires_at = if flag_duration == 0 {
+            0
+        } else {
+            now.saturating_add(flag_duration)
+        };
+    } else {
+        clear_bundle_flag(bundle_tracker);
+    }
     
     Ok(())
 }
 
+/// Reset a tracker's bundling flag
+pub fn clear_bundle_flag(bundle_tracker: &mut BundleTracker) {
+    bundle_tracker.is_bundling = false;
+    bundle_tracker.flag_reason = BundleFlagReason::None;
+    bundle_tracker.flag_expires_at = 0;
+}
+
+/// Check that relationship accounts were supplied for every wallet a tracker was related to,
+/// so a re-evaluation cannot drop related holdings by omitting them
+pub fn covers_related_wallets<'info>(
+    bundle_tracker: &BundleTracker,
+    remaining_accounts: &'info [AccountInfo<'info>]
+) -> Result<bool> {
+    let mut supplied: Vec<Pubkey> = Vec::new();
+    
+    for accounts in remaining_accounts.chunks(RELATED_WALLET_ACCOUNTS) {
+        let relationship = Account::<WalletRelationship>::try_from(&accounts[0])?;
+        supplied.push(relationship.wallet_a);
+        supplied.push(relationship.wallet_b);
+    }
+    
+    Ok(bundle_tracker
+        .related_wallets
+        .iter()
+        .all(|related_wallet| supplied.contains(related_wallet)))
+}
+
 /// Check if two wallets are related based on their relationship strength
 pub fn are_wallets_related(
     relationship: &Account<WalletRelationship>,
     relationship_threshold: u16
 ) -> Result<bool> {
+    // Relationships fade unless they keep being observed
+    let strength = decay_relationship_strength(
+        relationship.relationship_strength,
+        relationship.last_transaction,
+        Clock::get()?.unix_timestamp
+    );
+    
     // Check if the relationship strength exceeds the threshold
-    Ok(relationship.relationship_strength >= relationship_threshold)
+    Ok(strength >= relationship_threshold)
+}
+
+/// Halve a relationship's strength for every half-life elapsed since it was last observed
+pub fn decay_relationship_strength(strength: u16, last_transaction: i64, now: i64) -> u16 {
+    let elapsed = now.saturating_sub(last_transaction).max(0);
+    let halvings = elapsed / RELATIONSHIP_HALF_LIFE;
+    
+    if halvings >= 16 {
+        return 0;
+    }
+    
+    strength >> halvings
+}
+
+/// Latest slot at which a tracker recorded a relationship signal
+pub fn latest_signal_slot(bundle_tracker: &BundleTracker) -> u64 {
+    bundle_tracker.last_buy_slot.max(bundle_tracker.funding_slot)
+}
+
+/// Score the evidence that two wallets are controlled by the same party from the
+/// signals recorded on their bundle trackers (in basis points). Only signals observed
+/// after `since_slot`, the last slot already scored, count.
+pub fn score_relationship_evidence(
+    tracker_a: &BundleTracker,
+    tracker_b: &BundleTracker,
+    funding_window_slots: u64,
+    since_slot: u64
+) -> u16 {
+    let mut evidence: u16 = 0;
+    
+    // Both wallets bought in the same slot
+    if tracker_a.last_buy_slot > since_slot && tracker_a.last_buy_slot == tracker_b.last_buy_slot {
+        evidence = evidence.saturating_add(SAME_SLOT_WEIGHT);
+    }
+    
+    // A third party paid the transaction fees for both wallets
+    if tracker_a.last_buy_slot.max(tracker_b.last_buy_slot) > since_slot
+        && tracker_a.fee_payer != Pubkey::default()
+        && tracker_a.fee_payer == tracker_b.fee_payer
+        && tracker_a.fee_payer != tracker_a.wallet
+        && tracker_b.fee_payer != tracker_b.wallet
+    {
+        evidence = evidence.saturating_add(SHARED_FEE_PAYER_WEIGHT);
+    }
+    
+    // Both wallets were funded by the same source within the funding window
+    if let (Some(source_a), Some(source_b)) = (tracker_a.funding_source, tracker_b.funding_source) {
+        if source_a == source_b
+            && tracker_a.funding_slot.max(tracker_b.funding_slot) > since_slot
+            && tracker_a.funding_slot.abs_diff(tracker_b.funding_slot) <= funding_window_slots
+        {
+            evidence = evidence.saturating_add(COMMON_FUNDING_WEIGHT);
+        }
+    }
+    
+    evidence.min(MAX_RELATIONSHIP_STRENGTH)
+}
+
+/// Record the timing, fee payer and funding signals of a buy on the trader's bundle tracker
+pub fn record_trade_signals(
+    bundle_tracker: &mut Account<BundleTracker>,
+    fee_payer: &Pubkey,
+    instructions_sysvar: &AccountInfo,
+    slot: u64
+) -> Result<()> {
+    bundle_tracker.last_buy_slot = slot;
+    bundle_tracker.fee_payer = *fee_payer;
+    
+    // Keep the last observed funding source until a new one is seen
+    if let Some(source) = find_funding_source(instructions_sysvar, &bundle_tracker.wallet)? {
+        bundle_tracker.funding_source = Some(source);
+        bundle_tracker.funding_slot = slot;
+    }
+    
+    Ok(())
+}
+
+/// Find a wallet that sent SOL to `wallet` earlier in the current transaction
+pub fn find_funding_source(instructions_sysvar: &AccountInfo, wallet: &Pubkey) -> Result<Option<Pubkey>> {
+    // System program Transfer: u32 discriminant 2 followed by u64 lamports
+    const TRANSFER_DISCRIMINANT: [u8; 4] = 2u32.to_le_bytes();
+    
+    let current_index = load_current_index_checked(instructions_sysvar)? as usize;
+    
+    for index in 0..current_index {
+        let instruction = load_instruction_at_checked(index, instructions_sysvar)?;
+        
+        if instruction.program_id != anchor_lang::system_program::ID
+            || instruction.data.len() != 12
+            || instruction.data[..4] != TRANSFER_DISCRIMINANT
+            || instruction.accounts.len() < 2
+        {
+            continue;
+        }
+        
+        let from = instruction.accounts[0].pubkey;
+        if instruction.accounts[1].pubkey == *wallet && from != *wallet {
+            return Ok(Some(from));
+        }
+    }
+    
+    Ok(None)
+}
+
+/// Update a trader's bundle tracker with the combined holdings of every wallet related to it,
+/// and merge the resulting cluster into the related wallets' trackers.
+///
+/// `remaining_accounts` holds one (relationship, related token account, related bundle tracker,
+/// related exempt address) group per candidate wallet. Relationships below
+/// `relationship_threshold` are ignored, and wallets exempt from bundling neither count
+/// toward the cluster nor get flagged. The exempt address PDA must be passed even when it
+/// has not been created.
+pub fn update_bundle_cluster<'info>(
+    bundle_tracker: &mut Account<'info, BundleTracker>,
+    wallet: &Pubkey,
+    mint: &Pubkey,
+    wallet_balance: u64,
+    remaining_accounts: &'info [AccountInfo<'info>],
+    relationship_threshold: u16,
+    threshold_percentage: u16,
+    total_supply: u64,
+    flag_duration: i64,
+    exemption: Option<&ExemptAddress>
+) -> Result<()> {
+    if remaining_accounts.len() % RELATED_WALLET_ACCOUNTS != 0 {
+        return Err(error!(ErrorCode::InvalidRelationshipAccounts));
+    }
+    
+    let mut members: Vec<(Pubkey, &'info AccountInfo<'info>)> = Vec::new();
+    let mut total_bundle_balance = wallet_balance;
+    let vesting_escrow = vesting_escrow_address(mint);
+    
+    for accounts in remaining_accounts.chunks(RELATED_WALLET_ACCOUNTS) {
+        let relationship = Account::<WalletRelationship>::try_from(&accounts[0])?;
+        if relationship.mint != *mint {
+            return Err(error!(ErrorCode::InvalidRelationshipAccounts));
+        }
+        
+        // The relationship must involve the trading wallet
+        let related_wallet = if relationship.wallet_a == *wallet {
+            relationship.wallet_b
+        } else if relationship.wallet_b == *wallet {
+            relationship.wallet_a
+        } else {
+            return Err(error!(ErrorCode::InvalidRelationshipAccounts));
+        };
+        
+        // Locked creator allocations are disclosed, not bundled holdings
+        if related_wallet == vesting_escrow {
+            continue;
+        }
+        
+        if !are_wallets_related(&relationship, relationship_threshold)? {
+            continue;
+        }
+        
+        // Allowlisted wallets such as pools hold tokens for everyone
+        let (expected_exempt, _) = Pubkey::find_program_address(
+            &[b"exempt", related_wallet.as_ref()],
+            &crate::ID,
+        );
+        if accounts[3].key() != expected_exempt {
+            return Err(error!(ErrorCode::InvalidRelationshipAccounts));
+        }
+        let related_exemption = load_exemption(Some(&accounts[3]), &related_wallet, &crate::ID)?;
+        if has_exemption(related_exemption.as_ref(), &related_wallet, EXEMPT_BUNDLING) {
+            continue;
+        }
+        
+        if members.iter().any(|(member, _)| *member == related_wallet) {
+            return Err(error!(ErrorCode::InvalidRelationshipAccounts));
+        }
+        
+        if members.len() >= MAX_RELATED_WALLETS {
+            return Err(error!(ErrorCode::TooManyRelatedWallets));
+        }
+        
+        // Add the related wallet's holdings to the cluster
+        let token_account = InterfaceAccount::<TokenAccount>::try_from(&accounts[1])?;
+        if token_account.mint != *mint || token_account.owner != related_wallet {
+            return Err(error!(ErrorCode::InvalidRelationshipAccounts));
+        }
+        
+        total_bundle_balance = total_bundle_balance
+            .checked_add(token_account.amount)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+        
+        members.push((related_wallet, &accounts[2]));
+    }
+    
+    let related_wallets: Vec<Pubkey> = members.iter().map(|(member, _)| *member).collect();
+    
+    update_bundle_tracker(
+        bundle_tracker,
+        wallet,
+        mint,
+        related_wallets.clone(),
+        total_bundle_balance,
+        threshold_percentage,
+        total_supply,
+        BundleFlagReason::Threshold,
+        flag_duration,
+        exemption
+    )?;
+    
+    // Merge the cluster into each related wallet's tracker
+    for (related_wallet, tracker_info) in members {
+        let (expected_tracker, _) = Pubkey::find_program_address(
+            &[b"bundle", mint.as_ref(), related_wallet.as_ref()],
+            &crate::ID,
+        );
+        if tracker_info.key() != expected_tracker {
+            return Err(error!(ErrorCode::InvalidRelationshipAccounts));
+        }
+        
+        // Wallets that have never traded have no tracker yet
+        if tracker_info.owner != &crate::ID {
+            continue;
+        }
+        
+        let mut cluster: Vec<Pubkey> = related_wallets
+            .iter()
+            .filter(|member| **member != related_wallet)
+            .copied()
+            .collect();
+        cluster.push(*wallet);
+        
+        // Exempt wallets were left out of the cluster above
+        let mut related_tracker = Account::<BundleTracker>::try_from(tracker_info)?;
+        update_bundle_tracker(
+            &mut related_tracker,
+            &related_wallet,
+            mint,
+            cluster,
+            total_bundle_balance,
+            threshold_percentage,
+            total_supply,
+            BundleFlagReason::Threshold,
+            flag_duration,
+            None
+        )?;
+        related_tracker.exit(&crate::ID)?;
+    }
+    
+    Ok(())
+}
+
+/// Re-evaluate a cluster's bundling status from its aggregate balance
+pub fn refresh_bundle_cluster(
+    cluster: &mut Account<BundleCluster>,
+    threshold_percentage: u16,
+    total_supply: u64
+) -> Result<()> {
+    cluster.last_updated = Clock::get()?.unix_timestamp;
+    
+    if total_supply == 0 {
+        cluster.is_bundling = cluster.force_bundling;
+        return Ok(());
+    }
+    
+    let bundle_percentage = calculate_bundle_percentage(
+        cluster.total_balance,
+        total_supply
+    )?;
+    cluster.is_bundling = cluster.force_bundling || bundle_percentage > threshold_percentage;
+    
+    Ok(())
+}
+
+/// Point a new membership at a cluster
+pub fn join_bundle_cluster(
+    cluster: &mut Account<BundleCluster>,
+    membership: &mut Account<ClusterMembership>,
+    wallet: &Pubkey,
+    mint: &Pubkey
+) -> Result<()> {
+    membership.mint = *mint;
+    membership.wallet = *wallet;
+    membership.cluster = cluster.key();
+    membership.balance = 0;
+    
+    cluster.member_count = cluster.member_count
+        .checked_add(1)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+    
+    Ok(())
+}
+
+/// Take a membership's balance and count out of its cluster before the membership
+/// is closed
+pub fn leave_bundle_cluster(
+    cluster: &mut Account<BundleCluster>,
+    membership: &Account<ClusterMembership>,
+    threshold_percentage: u16,
+    total_supply: u64
+) -> Result<()> {
+    if membership.cluster != cluster.key() {
+        return Err(error!(ErrorCode::ClusterMismatch));
+    }
+    
+    cluster.total_balance = cluster.total_balance
+        .checked_sub(membership.balance)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+    cluster.member_count = cluster.member_count
+        .checked_sub(1)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+    
+    refresh_bundle_cluster(cluster, threshold_percentage, total_supply)
+}
+
+/// Apply a member's new token balance to its cluster's aggregate balance
+pub fn update_cluster_member_balance(
+    cluster: &mut Account<BundleCluster>,
+    membership: &mut Account<ClusterMembership>,
+    new_balance: u64,
+    threshold_percentage: u16,
+    total_supply: u64
+) -> Result<()> {
+    if membership.cluster != cluster.key() {
+        return Err(error!(ErrorCode::ClusterMismatch));
+    }
+    
+    // Swap the member's previous contribution for its new balance
+    cluster.total_balance = cluster.total_balance
+        .checked_sub(membership.balance)
+        .ok_or(error!(ErrorCode::MathOverflow))?
+        .checked_add(new_balance)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+    membership.balance = new_balance;
+    
+    refresh_bundle_cluster(cluster, threshold_percentage, total_supply)
+}
+
+/// Load a wallet's cluster membership from its derived PDA. Wallets that never joined a
+/// cluster have no membership account.
+pub fn load_membership<'info>(membership_info: &AccountInfo<'info>) -> Result<Option<Account<'info, ClusterMembership>>> {
+    if membership_info.owner != &crate::ID || membership_info.data_is_empty() {
+        return Ok(None);
+    }
+    
+    Ok(Some(Account::<ClusterMembership>::try_from(membership_info)?))
+}
+
+/// Create a wallet's cluster membership PDA the first time it is tagged into a cluster.
+/// The account is funded the way Anchor's `init` does, so lamports sent to the address
+/// ahead of time cannot block its creation.
+pub fn create_membership<'info>(
+    membership_info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    mint: &Pubkey,
+    wallet: &Pubkey,
+    bump: u8,
+    system_program_info: &AccountInfo<'info>,
+) -> Result<Account<'info, ClusterMembership>> {
+    let space = 8 + ClusterMembership::INIT_SPACE;
+    let rent = Rent::get()?.minimum_balance(space);
+    let membership_seeds: &[&[u8]] = &[b"membership", mint.as_ref(), wallet.as_ref(), &[bump]];
+    
+    if membership_info.lamports() == 0 {
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program_info.clone(),
+                system_program::CreateAccount {
+                    from: payer.clone(),
+                    to: membership_info.clone(),
+                },
+                &[membership_seeds],
+            ),
+            rent,
+            space as u64,
+            &crate::ID,
+        )?;
+    } else {
+        let top_up = rent.saturating_sub(membership_info.lamports());
+        if top_up > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    system_program_info.clone(),
+                    system_program::Transfer {
+                        from: payer.clone(),
+                        to: membership_info.clone(),
+                    },
+                ),
+                top_up,
+            )?;
+        }
+        
+        system_program::allocate(
+            CpiContext::new_with_signer(
+                system_program_info.clone(),
+                system_program::Allocate {
+                    account_to_allocate: membership_info.clone(),
+                },
+                &[membership_seeds],
+            ),
+            space as u64,
+        )?;
+        system_program::assign(
+            CpiContext::new_with_signer(
+                system_program_info.clone(),
+                system_program::Assign {
+                    account_to_assign: membership_info.clone(),
+                },
+                &[membership_seeds],
+            ),
+            &crate::ID,
+        )?;
+    }
+    
+    Ok(Account::try_from_unchecked(membership_info)?)
+}
+
+/// Apply a wallet's new balance to its cluster through the wallet's derived membership
+/// PDA. Members must pass the cluster their membership points at. Returns whether the
+/// wallet belongs to a cluster.
+pub fn update_member_cluster<'info>(
+    membership_info: &AccountInfo<'info>,
+    cluster: Option<&mut Account<'info, BundleCluster>>,
+    new_balance: u64,
+    threshold_percentage: u16,
+    total_supply: u64
+) -> Result<bool> {
+    let Some(mut membership) = load_membership(membership_info)? else {
+        return Ok(false);
+    };
+    
+    if membership.cluster == Pubkey::default() {
+        return Ok(false);
+    }
+    
+    let cluster = cluster.ok_or(error!(ErrorCode::ClusterMismatch))?;
+    update_cluster_member_balance(cluster, &mut membership, new_balance, threshold_percentage, total_supply)?;
+    membership.exit(&crate::ID)?;
+    
+    Ok(true)
+}
+
+/// Move the given memberships from one cluster to another, carrying their balances with
+/// them. A source cluster left without members is closed, its rent going to `rent_recipient`.
+pub fn move_cluster_members<'info>(
+    source: &mut Account<'info, BundleCluster>,
+    target: &mut Account<'info, BundleCluster>,
+    memberships: &'info [AccountInfo<'info>],
+    rent_recipient: &AccountInfo<'info>,
+    threshold_percentage: u16,
+    total_supply: u64
+) -> Result<()> {
+    if source.key() == target.key() || source.mint != target.mint {
+        return Err(error!(ErrorCode::ClusterMismatch));
+    }
+    
+    for membership_info in memberships {
+        let mut membership = Account::<ClusterMembership>::try_from(membership_info)?;
+        if membership.cluster != source.key() {
+            return Err(error!(ErrorCode::ClusterMismatch));
+        }
+        
+        source.total_balance = source.total_balance
+            .checked_sub(membership.balance)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+        source.member_count = source.member_count
+            .checked_sub(1)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+        
+        target.total_balance = target.total_balance
+            .checked_add(membership.balance)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+        target.member_count = target.member_count
+            .checked_add(1)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+        
+        membership.cluster = target.key();
+        membership.exit(&crate::ID)?;
+    }
+    
+    // Moved members carry their buy cooldown with them
+    target.last_buy_at = target.last_buy_at.max(source.last_buy_at);
+    
+    refresh_bundle_cluster(source, threshold_percentage, total_supply)?;
+    refresh_bundle_cluster(target, threshold_percentage, total_supply)?;
+    
+    // The project's sniper cluster stays open for later snipers to join
+    if source.member_count == 0 && source.cluster_id != SNIPER_CLUSTER_ID {
+        source.close(rent_recipient.clone())?;
+    }
+    
+    Ok(())
+}
+
+/// Record who paid for a tracking account the first time it is written, so closing it
+/// refunds them
+pub fn record_rent_payer(rent_payer: &mut Pubkey, payer: &Pubkey) {
+    if *rent_payer == Pubkey::default() {
+        *rent_payer = *payer;
+    }
+}
+
+/// Close a tracking account, returning its rent to `recipient`
+pub fn close_tracking_account(account: &AccountInfo, recipient: &AccountInfo) -> Result<()> {
+    let lamports = account.lamports();
+    
+    **recipient.try_borrow_mut_lamports()? = recipient
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+    **account.try_borrow_mut_lamports()? = 0;
+    
+    // Hand the emptied account back to the system program
+    account.assign(&anchor_lang::system_program::ID);
+    account.realloc(0, false)?;
+    
+    Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::ExemptAddress;
+
+/// Exempt from the external transfer fee
+pub const EXEMPT_FEES: u8 = 1 << 0;
+
+/// Exempt from bundle detection
+pub const EXEMPT_BUNDLING: u8 = 1 << 1;
+
+/// Exempt from max-wallet, max-buy and cooldown limits
+pub const EXEMPT_LIMITS: u8 = 1 << 2;
+
+/// All exemption scopes
+pub const EXEMPT_ALL: u8 = EXEMPT_FEES | EXEMPT_BUNDLING | EXEMPT_LIMITS;
+
+/// Check if an exemption entry covers `address` for the given scope
+pub fn has_exemption(exemption: Option<&ExemptAddress>, address: &Pubkey, scope: u8) -> bool {
+    match exemption {
+        Some(exemption) => exemption.address == *address && exemption.scopes & scope != 0,
+        None => false,
+    }
+}
+
+/// Look up the exemption PDA for `address` among raw accounts, as passed to the transfer hook
+pub fn load_exemption(
+    exempt_info: Option<&AccountInfo>,
+    address: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<Option<ExemptAddress>> {
+    let Some(exempt_info) = exempt_info else {
+        return Ok(None);
+    };
+    
+    if exempt_info.owner != program_id || exempt_info.data_is_empty() {
+        return Ok(None);
+    }
+    
+    // Only the canonical PDA for this address counts
+    let (expected, _) = Pubkey::find_program_address(&[b"exempt", address.as_ref()], program_id);
+    if *exempt_info.key != expected {
+        return Ok(None);
+    }
+    
+    let data = exempt_info.try_borrow_data()?;
+    let exemption = ExemptAddress::try_deserialize(&mut &data[..])?;
+    Ok(Some(exemption))
+}
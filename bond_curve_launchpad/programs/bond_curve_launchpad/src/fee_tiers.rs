@@ -0,0 +1,164 @@
+use anchor_lang::prelude::*;
+
+use crate::{ErrorCode, FeeTier, LaunchpadConfig, TraderStats};
+
+/// Maximum number of fee tiers in the launchpad config
+pub const MAX_FEE_TIERS: usize = 4;
+
+/// Number of days the rolling trader volume covers
+pub const VOLUME_WINDOW_DAYS: usize = 30;
+
+/// Seconds in one volume bucket
+pub const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// Check that tiers are ordered by volume and only ever lower the base fee
+pub fn validate_fee_tiers(fee_tiers: &[FeeTier], trading_fee_bps: u16) -> Result<()> {
+    if fee_tiers.len() > MAX_FEE_TIERS {
+        return Err(error!(ErrorCode::InvalidFeeTiers));
+    }
+    
+    for (index, tier) in fee_tiers.iter().enumerate() {
+        if tier.fee_bps > trading_fee_bps {
+            return Err(error!(ErrorCode::InvalidFeeTiers));
+        }
+        
+        if let Some(previous) = index.checked_sub(1).map(|previous| &fee_tiers[previous]) {
+            if tier.min_volume <= previous.min_volume || tier.fee_bps > previous.fee_bps {
+                return Err(error!(ErrorCode::InvalidFeeTiers));
+            }
+        }
+    }
+    
+    Ok(())
+}
+
+/// Fee in basis points for a trader with the given rolling volume
+pub fn effective_fee_bps(config: &LaunchpadConfig, rolling_volume: u64) -> u16 {
+    config.fee_tiers
+        .iter()
+        .rev()
+        .find(|tier| rolling_volume >= tier.min_volume)
+        .map(|tier| tier.fee_bps.min(config.trading_fee_bps))
+        .unwrap_or(config.trading_fee_bps)
+}
+
+/// Sum of the trader's volume over the last `VOLUME_WINDOW_DAYS` days
+pub fn rolling_volume(trader_stats: &TraderStats, now: i64) -> u64 {
+    let today = now / SECONDS_PER_DAY;
+    let window_start = today - (VOLUME_WINDOW_DAYS as i64 - 1);
+    
+    (window_start.max(trader_stats.last_day - (VOLUME_WINDOW_DAYS as i64 - 1))..=trader_stats.last_day)
+        .map(|day| trader_stats.daily_volumes[bucket(day)])
+        .fold(0u64, |total, volume| total.saturating_add(volume))
+}
+
+/// Add a trade's volume to today's bucket, clearing buckets for days without trades
+pub fn record_volume(trader_stats: &mut TraderStats, wallet: &Pubkey, volume: u64, now: i64) -> Result<()> {
+    let today = now / SECONDS_PER_DAY;
+    
+    if trader_stats.wallet == Pubkey::default() {
+        trader_stats.wallet = *wallet;
+        trader_stats.last_day = today;
+    }
+    
+    // Buckets are reused every VOLUME_WINDOW_DAYS days, so stale ones are zeroed first
+    if today > trader_stats.last_day {
+        let first_stale = (trader_stats.last_day + 1).max(today - (VOLUME_WINDOW_DAYS as i64 - 1));
+        for day in first_stale..=today {
+            trader_stats.daily_volumes[bucket(day)] = 0;
+        }
+        trader_stats.last_day = today;
+    }
+    
+    let today_bucket = bucket(today);
+    trader_stats.daily_volumes[today_bucket] = trader_stats.daily_volumes[today_bucket]
+        .checked_add(volume)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+    trader_stats.total_volume = trader_stats.total_volume
+        .checked_add(volume)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+    
+    Ok(())
+}
+
+fn bucket(day: i64) -> usize {
+    day.rem_euclid(VOLUME_WINDOW_DAYS as i64) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LAUNCH_DAY: i64 = 19_700;
+
+    fn at_day(day: i64) -> i64 {
+        (LAUNCH_DAY + day) * SECONDS_PER_DAY
+    }
+
+    fn stats_fixture() -> TraderStats {
+        TraderStats {
+            wallet: Pubkey::default(),
+            total_volume: 0,
+            daily_volumes: [0; VOLUME_WINDOW_DAYS],
+            last_day: 0,
+        }
+    }
+
+    #[test]
+    fn sums_the_last_thirty_days() {
+        let mut trader_stats = stats_fixture();
+        let wallet = Pubkey::new_unique();
+        
+        record_volume(&mut trader_stats, &wallet, 100, at_day(0)).unwrap();
+        record_volume(&mut trader_stats, &wallet, 200, at_day(0) + 60).unwrap();
+        record_volume(&mut trader_stats, &wallet, 300, at_day(29)).unwrap();
+        assert_eq!(trader_stats.wallet, wallet);
+        assert_eq!(rolling_volume(&trader_stats, at_day(29)), 600);
+        
+        // The first day falls out of the window, though not out of the lifetime total
+        assert_eq!(rolling_volume(&trader_stats, at_day(30)), 300);
+        assert_eq!(trader_stats.total_volume, 600);
+    }
+
+    #[test]
+    fn short_gaps_only_clear_the_skipped_days() {
+        let mut trader_stats = stats_fixture();
+        let wallet = Pubkey::new_unique();
+        
+        record_volume(&mut trader_stats, &wallet, 100, at_day(0)).unwrap();
+        record_volume(&mut trader_stats, &wallet, 200, at_day(20)).unwrap();
+        assert_eq!(rolling_volume(&trader_stats, at_day(20)), 300);
+        
+        // Day 30 reuses day 0's bucket, which must be cleared before it is added to
+        record_volume(&mut trader_stats, &wallet, 50, at_day(30)).unwrap();
+        assert_eq!(rolling_volume(&trader_stats, at_day(30)), 250);
+    }
+
+    #[test]
+    fn gaps_longer_than_the_window_clear_every_bucket() {
+        let mut trader_stats = stats_fixture();
+        let wallet = Pubkey::new_unique();
+        
+        for day in 0..VOLUME_WINDOW_DAYS as i64 {
+            record_volume(&mut trader_stats, &wallet, 100, at_day(day)).unwrap();
+        }
+        assert_eq!(rolling_volume(&trader_stats, at_day(29)), 3_000);
+        
+        // Nothing traded in the window, even before the buckets are cleared
+        assert_eq!(rolling_volume(&trader_stats, at_day(75)), 0);
+        
+        record_volume(&mut trader_stats, &wallet, 40, at_day(75)).unwrap();
+        assert_eq!(rolling_volume(&trader_stats, at_day(75)), 40);
+        assert_eq!(trader_stats.daily_volumes.iter().sum::<u64>(), 40);
+        assert_eq!(trader_stats.total_volume, 3_040);
+    }
+
+    #[test]
+    fn rejects_overflowing_volume() {
+        let mut trader_stats = stats_fixture();
+        let wallet = Pubkey::new_unique();
+        
+        record_volume(&mut trader_stats, &wallet, u64::MAX, at_day(0)).unwrap();
+        assert!(record_volume(&mut trader_stats, &wallet, 1, at_day(1)).is_err());
+    }
+}
@@ -0,0 +1,271 @@
+use anchor_lang::prelude::*;
+
+use crate::{AuctionCommitment, ErrorCode, LaunchAuction, TokenProject};
+use crate::bond_curve::calculate_tokens_for_cost;
+
+/// Seconds after `ends_at` an auction has to be settled. Past it, commitments can be
+/// withdrawn in full instead.
+pub const AUCTION_SETTLEMENT_WINDOW_SECS: i64 = 24 * 60 * 60;
+
+/// Result of clearing a launch auction against the curve
+pub struct AuctionClearing {
+    pub tokens: u64,
+    pub curve_cost: u64,
+    pub creator_fee: u64,
+    pub platform_fee: u64,
+}
+
+/// An auction launch mints nothing before its clearing buy, so it takes no dev-buy.
+/// Creators commit to the auction like everyone else.
+pub fn validate_auction_dev_buy(auction_ends_at: Option<i64>, initial_buy_amount: Option<u64>) -> Result<()> {
+    if auction_ends_at.is_some() && matches!(initial_buy_amount, Some(amount) if amount > 0) {
+        return Err(error!(ErrorCode::UnsupportedDevBuyAuction));
+    }
+    
+    Ok(())
+}
+
+/// Add a wallet's SOL commitment to the auction
+pub fn record_commitment(
+    launch_auction: &mut LaunchAuction,
+    commitment: &mut AuctionCommitment,
+    wallet: &Pubkey,
+    amount: u64,
+) -> Result<()> {
+    // First commitment from this wallet
+    if commitment.amount == 0 {
+        commitment.mint = launch_auction.mint;
+        commitment.wallet = *wallet;
+        launch_auction.commitment_count = launch_auction.commitment_count
+            .checked_add(1)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+    }
+    
+    commitment.amount = commitment.amount
+        .checked_add(amount)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+    launch_auction.total_committed = launch_auction.total_committed
+        .checked_add(amount)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+    
+    Ok(())
+}
+
+/// Take a withdrawn commitment out of the auction totals
+pub fn remove_commitment(launch_auction: &mut LaunchAuction, amount: u64) -> Result<()> {
+    launch_auction.total_committed = launch_auction.total_committed
+        .checked_sub(amount)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+    launch_auction.commitment_count = launch_auction.commitment_count
+        .checked_sub(1)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+    
+    Ok(())
+}
+
+/// Whether an auction went unsettled past its settlement window
+pub fn is_settlement_expired(launch_auction: &LaunchAuction, now: i64) -> bool {
+    !launch_auction.settled && now >= launch_auction.ends_at.saturating_add(AUCTION_SETTLEMENT_WINDOW_SECS)
+}
+
+/// Whether commitments can be withdrawn in full: the auction was never settled, and
+/// either its settlement window passed or the launch was cancelled
+pub fn is_withdrawable(launch_auction: &LaunchAuction, project: &TokenProject, now: i64) -> bool {
+    !launch_auction.settled && (project.is_cancelled || is_settlement_expired(launch_auction, now))
+}
+
+/// Clear every commitment in one curve purchase. The trading fee comes off the top,
+/// the rest buys as many tokens as it can at the current supply, and whatever the
+/// curve does not use is left for refunds. Everyone pays the same price per token.
+pub fn clear_launch_auction(
+    launch_auction: &mut LaunchAuction,
+    project: &TokenProject,
+    trading_fee_bps: u16,
+) -> Result<AuctionClearing> {
+    let total_fee = launch_auction.total_committed
+        .checked_mul(trading_fee_bps as u64)
+        .ok_or(error!(ErrorCode::MathOverflow))?
+        .checked_div(10000)
+        .ok_or(error!(ErrorCode::DivisionByZero))?;
+    let budget = launch_auction.total_committed
+        .checked_sub(total_fee)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+    
+    let (tokens, curve_cost) = calculate_tokens_for_cost(&project.curve_params, project.supply, budget)?;
+    
+    // Auctions too small to buy a single token are refunded in full, fee-free
+    let total_fee = if tokens == 0 { 0 } else { total_fee };
+    let creator_fee = total_fee.checked_div(2).ok_or(error!(ErrorCode::DivisionByZero))?;
+    let platform_fee = total_fee.checked_sub(creator_fee).ok_or(error!(ErrorCode::MathOverflow))?;
+    
+    launch_auction.settled = true;
+    launch_auction.clearing_tokens = tokens;
+    launch_auction.refund_pool = launch_auction.total_committed
+        .checked_sub(total_fee)
+        .ok_or(error!(ErrorCode::MathOverflow))?
+        .checked_sub(curve_cost)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+    
+    Ok(AuctionClearing {
+        tokens,
+        curve_cost,
+        creator_fee,
+        platform_fee,
+    })
+}
+
+/// Tokens and refund owed to a commitment, pro rata to its share of the total. The
+/// last commitment distributed takes the rounding remainder.
+pub fn allocate_commitment(launch_auction: &mut LaunchAuction, commitment_amount: u64) -> Result<(u64, u64)> {
+    let (tokens, refund) = if launch_auction.commitment_count == 1 {
+        (
+            launch_auction.clearing_tokens
+                .checked_sub(launch_auction.distributed_tokens)
+                .ok_or(error!(ErrorCode::MathOverflow))?,
+            launch_auction.refund_pool
+                .checked_sub(launch_auction.distributed_refund)
+                .ok_or(error!(ErrorCode::MathOverflow))?,
+        )
+    } else {
+        (
+            pro_rata(launch_auction.clearing_tokens, commitment_amount, launch_auction.total_committed)?,
+            pro_rata(launch_auction.refund_pool, commitment_amount, launch_auction.total_committed)?,
+        )
+    };
+    
+    launch_auction.commitment_count = launch_auction.commitment_count
+        .checked_sub(1)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+    launch_auction.distributed_tokens = launch_auction.distributed_tokens
+        .checked_add(tokens)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+    launch_auction.distributed_refund = launch_auction.distributed_refund
+        .checked_add(refund)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+    
+    Ok((tokens, refund))
+}
+
+fn pro_rata(total: u64, share: u64, whole: u64) -> Result<u64> {
+    let amount = (total as u128)
+        .checked_mul(share as u128)
+        .ok_or(error!(ErrorCode::MathOverflow))?
+        .checked_div(whole as u128)
+        .ok_or(error!(ErrorCode::DivisionByZero))?;
+    
+    Ok(amount as u64)
+}
+
+/// Pay out SOL escrowed as lamports on a program-owned account, such as a launch
+/// auction or a limit order, without a system transfer
+pub fn release_escrowed_lamports(escrow: &AccountInfo, recipient: &AccountInfo, amount: u64) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    
+    **escrow.try_borrow_mut_lamports()? = escrow
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(error!(ErrorCode::InsufficientFunds))?;
+    **recipient.try_borrow_mut_lamports()? = recipient
+        .lamports()
+        .checked_add(amount)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::project_fixture;
+
+    fn auction_fixture(total_committed: u64, commitment_count: u32) -> LaunchAuction {
+        LaunchAuction {
+            mint: Pubkey::new_unique(),
+            ends_at: 1_700_000_000,
+            total_committed,
+            commitment_count,
+            settled: false,
+            clearing_tokens: 0,
+            refund_pool: 0,
+            distributed_tokens: 0,
+            distributed_refund: 0,
+        }
+    }
+
+    #[test]
+    fn clears_auction_at_one_price() {
+        let mut launch_auction = auction_fixture(10_000, 3);
+        
+        // 1% fee leaves 9,900 lamports for 1,000-lamport tokens
+        let clearing = clear_launch_auction(&mut launch_auction, &project_fixture(), 100).unwrap();
+        assert_eq!(clearing.tokens, 9);
+        assert_eq!(clearing.curve_cost, 9_000);
+        assert_eq!(clearing.creator_fee + clearing.platform_fee, 100);
+        assert!(launch_auction.settled);
+        assert_eq!(launch_auction.refund_pool, 900);
+    }
+
+    #[test]
+    fn refunds_auctions_too_small_for_a_token() {
+        let mut launch_auction = auction_fixture(999, 1);
+        
+        let clearing = clear_launch_auction(&mut launch_auction, &project_fixture(), 100).unwrap();
+        assert_eq!(clearing.tokens, 0);
+        assert_eq!(clearing.creator_fee + clearing.platform_fee, 0);
+        assert_eq!(launch_auction.refund_pool, 999);
+    }
+
+    #[test]
+    fn last_commitment_takes_the_rounding_remainder() {
+        let mut launch_auction = auction_fixture(10_000, 3);
+        clear_launch_auction(&mut launch_auction, &project_fixture(), 100).unwrap();
+        
+        // 3,000 / 10,000 of 9 tokens is 2.7, rounded down for all but the last
+        assert_eq!(allocate_commitment(&mut launch_auction, 3_000).unwrap(), (2, 270));
+        assert_eq!(allocate_commitment(&mut launch_auction, 3_000).unwrap(), (2, 270));
+        assert_eq!(allocate_commitment(&mut launch_auction, 4_000).unwrap(), (5, 360));
+        
+        assert_eq!(launch_auction.commitment_count, 0);
+        assert_eq!(launch_auction.distributed_tokens, launch_auction.clearing_tokens);
+        assert_eq!(launch_auction.distributed_refund, launch_auction.refund_pool);
+    }
+
+    #[test]
+    fn settlement_window_closes_after_ends_at() {
+        let mut launch_auction = auction_fixture(10_000, 3);
+        let deadline = launch_auction.ends_at + AUCTION_SETTLEMENT_WINDOW_SECS;
+        
+        assert!(!is_settlement_expired(&launch_auction, deadline - 1));
+        assert!(is_settlement_expired(&launch_auction, deadline));
+        
+        launch_auction.settled = true;
+        assert!(!is_settlement_expired(&launch_auction, deadline));
+    }
+
+    #[test]
+    fn cancelled_launches_release_unsettled_commitments() {
+        let mut launch_auction = auction_fixture(10_000, 3);
+        let mut project = project_fixture();
+        
+        assert!(!is_withdrawable(&launch_auction, &project, launch_auction.ends_at));
+        
+        project.is_cancelled = true;
+        assert!(is_withdrawable(&launch_auction, &project, launch_auction.ends_at));
+        
+        // Settled auctions distribute through settle_launch instead
+        launch_auction.settled = true;
+        assert!(!is_withdrawable(&launch_auction, &project, launch_auction.ends_at));
+    }
+
+    #[test]
+    fn auction_launches_take_no_dev_buy() {
+        assert!(validate_auction_dev_buy(Some(1_700_000_000), Some(1_000)).is_err());
+        
+        // A zero dev-buy is no dev-buy
+        assert!(validate_auction_dev_buy(Some(1_700_000_000), Some(0)).is_ok());
+        assert!(validate_auction_dev_buy(Some(1_700_000_000), None).is_ok());
+        assert!(validate_auction_dev_buy(None, Some(1_000)).is_ok());
+    }
+}
@@ -0,0 +1,168 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+
+use crate::{ErrorCode, TokenProject, WhitelistPhase, WhitelistPurchase};
+
+/// Launch slot of a scheduled project until its first public buy opens the sniping window
+pub const SCHEDULED_LAUNCH_SLOT: u64 = u64::MAX;
+
+/// Maximum depth of a whitelist Merkle proof
+pub const MAX_WHITELIST_PROOF_LEN: usize = 24;
+
+/// Who may buy a project's tokens at a given time
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LaunchPhase {
+    Pending,
+    Whitelist,
+    Public,
+}
+
+/// Check a launch schedule before it is stored on the project
+pub fn validate_launch_schedule(launch_at: i64, whitelist: &Option<WhitelistPhase>) -> Result<()> {
+    if let Some(whitelist) = whitelist {
+        if whitelist.public_open_at <= launch_at || whitelist.wallet_cap == 0 {
+            return Err(error!(ErrorCode::InvalidLaunchSchedule));
+        }
+    }
+    
+    Ok(())
+}
+
+/// Phase of a project's launch at `now`
+pub fn launch_phase(project: &TokenProject, now: i64) -> LaunchPhase {
+    if now < project.launch_at {
+        LaunchPhase::Pending
+    } else if project.whitelist_root.is_some() && now < project.public_open_at {
+        LaunchPhase::Whitelist
+    } else {
+        LaunchPhase::Public
+    }
+}
+
+/// Verify that `wallet` is a leaf of the whitelist tree. Leaves are `keccak(wallet)`
+/// and each pair of nodes is hashed in sorted order, so proofs carry no directions.
+pub fn verify_whitelist_proof(root: &[u8; 32], wallet: &Pubkey, proof: &[[u8; 32]]) -> bool {
+    if proof.len() > MAX_WHITELIST_PROOF_LEN {
+        return false;
+    }
+    
+    let leaf = keccak::hashv(&[wallet.as_ref()]).0;
+    let computed = proof.iter().fold(leaf, |node, sibling| {
+        if node <= *sibling {
+            keccak::hashv(&[&node, sibling]).0
+        } else {
+            keccak::hashv(&[sibling, &node]).0
+        }
+    });
+    
+    computed == *root
+}
+
+/// Add a whitelist-phase buy to the wallet's running total, enforcing the per-wallet cap
+pub fn record_whitelist_purchase(
+    whitelist_purchase: &mut WhitelistPurchase,
+    mint: &Pubkey,
+    wallet: &Pubkey,
+    amount: u64,
+    wallet_cap: u64,
+) -> Result<()> {
+    whitelist_purchase.mint = *mint;
+    whitelist_purchase.wallet = *wallet;
+    
+    let purchased = whitelist_purchase.purchased
+        .checked_add(amount)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+    
+    if purchased > wallet_cap {
+        return Err(error!(ErrorCode::WhitelistCapExceeded));
+    }
+    
+    whitelist_purchase.purchased = purchased;
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaf(wallet: &Pubkey) -> [u8; 32] {
+        keccak::hashv(&[wallet.as_ref()]).0
+    }
+
+    fn parent(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+        if left <= right {
+            keccak::hashv(&[left, right]).0
+        } else {
+            keccak::hashv(&[right, left]).0
+        }
+    }
+
+    /// A four-wallet tree, its root, and each wallet's proof
+    fn whitelist_fixture() -> (Vec<Pubkey>, [u8; 32], Vec<Vec<[u8; 32]>>) {
+        let wallets: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        let leaves: Vec<[u8; 32]> = wallets.iter().map(leaf).collect();
+        let left = parent(&leaves[0], &leaves[1]);
+        let right = parent(&leaves[2], &leaves[3]);
+        
+        let proofs = vec![
+            vec![leaves[1], right],
+            vec![leaves[0], right],
+            vec![leaves[3], left],
+            vec![leaves[2], left],
+        ];
+        
+        (wallets, parent(&left, &right), proofs)
+    }
+
+    #[test]
+    fn accepts_every_whitelisted_wallet() {
+        let (wallets, root, proofs) = whitelist_fixture();
+        
+        for (wallet, proof) in wallets.iter().zip(&proofs) {
+            assert!(verify_whitelist_proof(&root, wallet, proof));
+        }
+    }
+
+    #[test]
+    fn rejects_wallets_outside_the_tree() {
+        let (wallets, root, proofs) = whitelist_fixture();
+        
+        assert!(!verify_whitelist_proof(&root, &Pubkey::new_unique(), &proofs[0]));
+        
+        // Another wallet's proof does not fit
+        assert!(!verify_whitelist_proof(&root, &wallets[0], &proofs[2]));
+    }
+
+    #[test]
+    fn rejects_tampered_proofs() {
+        let (wallets, root, proofs) = whitelist_fixture();
+        
+        let mut proof = proofs[0].clone();
+        proof[1][0] ^= 1;
+        assert!(!verify_whitelist_proof(&root, &wallets[0], &proof));
+        
+        // A truncated proof stops at an inner node instead of the root
+        assert!(!verify_whitelist_proof(&root, &wallets[0], &proofs[0][..1]));
+    }
+
+    #[test]
+    fn single_wallet_tree_needs_no_proof() {
+        let wallet = Pubkey::new_unique();
+        
+        assert!(verify_whitelist_proof(&leaf(&wallet), &wallet, &[]));
+    }
+
+    #[test]
+    fn caps_proof_depth() {
+        let wallet = Pubkey::new_unique();
+        let root_for = |proof: &[[u8; 32]]| proof.iter().fold(leaf(&wallet), |node, sibling| parent(&node, sibling));
+        
+        let proof = [[7u8; 32]; MAX_WHITELIST_PROOF_LEN];
+        assert!(verify_whitelist_proof(&root_for(&proof), &wallet, &proof));
+        
+        // One level deeper is refused even though it hashes to its root
+        let proof = [[7u8; 32]; MAX_WHITELIST_PROOF_LEN + 1];
+        assert!(!verify_whitelist_proof(&root_for(&proof), &wallet, &proof));
+    }
+}
@@ -1,8 +1,11 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
-    token::{Mint, Token, TokenAccount},
+    token::Token,
+    token_2022::{self, Burn, MintTo, Token2022},
+    token_interface,
     associated_token::AssociatedToken,
 };
+use spl_token_metadata_interface::state::Field;
 use solana_program::{
     program::invoke,
     system_instruction,
@@ -12,11 +15,40 @@ mod bond_curve;
 mod wsol;
 mod transfer_hook;
 mod bundle_detection;
+mod sniper_detection;
+mod trading_limits;
+mod exemptions;
+mod migration;
+mod metadata;
+mod token_mint;
+mod quote;
+mod referrals;
+mod fee_tiers;
+mod vesting;
+mod launch_schedule;
+mod launch_auction;
+mod refunds;
+mod orders;
 
-use bond_curve::{calculate_buy_price, calculate_sell_price, calculate_current_price, calculate_market_cap, is_eligible_for_graduation};
-use wsol::{wrap_sol, unwrap_sol, get_wsol_mint};
-use transfer_hook::initialize_transfer_hook;
-use bundle_detection::{calculate_bundle_percentage, is_bundling, update_bundle_tracker, are_wallets_related};
+use transfer_hook::{extra_account_metas, write_extra_account_metas, process_transfer_hook};
+use spl_tlv_account_resolution::state::ExtraAccountMetaList;
+use bond_curve::{calculate_buy_price, calculate_sell_price, calculate_current_price, calculate_market_cap, is_eligible_for_graduation, scale_to_quote_decimals, normalize_curve_params, SOL_DECIMALS};
+use wsol::{wrap_sol, unwrap_sol, wrap_sol_signed, unwrap_sol_signed, get_wsol_mint};
+use token_mint::create_token_mint;
+use quote::{require_quote_accounts, transfer_quote, quote_graduation_market_cap};
+use orders::{validate_order, order_escrow, take_escrowed_tokens, check_executable};
+use refunds::{validate_min_raise, record_raise_progress, has_failed, is_cancelled, cancel_project, calculate_refund};
+use launch_auction::{validate_auction_dev_buy, record_commitment, remove_commitment, is_settlement_expired, is_withdrawable, clear_launch_auction, allocate_commitment, release_escrowed_lamports};
+use launch_schedule::{validate_launch_schedule, launch_phase, verify_whitelist_proof, record_whitelist_purchase, LaunchPhase, SCHEDULED_LAUNCH_SLOT};
+use vesting::{validate_vesting_schedule, max_premint_supply, initialize_vesting_escrow, claimable_amount};
+use fee_tiers::{effective_fee_bps, record_volume, rolling_volume, validate_fee_tiers, MAX_FEE_TIERS, VOLUME_WINDOW_DAYS};
+use referrals::{calculate_referral_fee, record_referral, MAX_REFERRAL_FEE_BPS};
+use bundle_detection::{MAX_RELATED_WALLETS, bundle_balance, cap_creator_allocation, calculate_bundle_percentage, is_bundling, is_flag_expired, update_bundle_tracker, covers_related_wallets, close_tracking_account, update_bundle_cluster, are_wallets_related, decay_relationship_strength, record_trade_signals, latest_signal_slot, score_relationship_evidence, update_cluster_member_balance, move_cluster_members, join_bundle_cluster, leave_bundle_cluster, load_membership, create_membership, update_member_cluster, record_rent_payer};
+use exemptions::{has_exemption, EXEMPT_ALL, EXEMPT_LIMITS};
+use trading_limits::{check_max_buy, check_max_wallet, check_buy_cooldown};
+use metadata::{validate_metadata, initialize_token_metadata, set_metadata_field};
+use migration::{upgrade_config, upgrade_project, write_migrated_account, CONFIG_VERSION, PROJECT_VERSION};
+use sniper_detection::{is_in_snipe_window, init_launch_ledger, init_sniper_cluster, record_launch_buy, calculate_snipe_tax, validate_launch_fee, calculate_launch_fee_bps, LAUNCH_LEDGER_SLOTS, SNIPER_CLUSTER_ID};
 
 declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
 
@@ -38,556 +70,4147 @@ pub mod bond_curve_launchpad {
         config.graduation_market_cap = graduation_market_cap;
         config.trading_fee_bps = 100; // 1% trading fee
         config.relationship_threshold = 300; // 3% relationship threshold
+        config.bundle_penalty_mode = BundlePenaltyMode::Block;
+        config.funding_window_slots = 150; // ~1 minute of slots
+        config.bundle_flag_duration = 7 * 24 * 60 * 60; // Flags expire after 7 days
+        config.snipe_window_slots = 0; // Sniper detection disabled
+        config.snipe_tax_bps = 0;
+        config.snipe_block = false;
+        config.version = CONFIG_VERSION;
+        config.referral_fee_bps = 0; // Referral payouts disabled
+        config.fee_tiers = Vec::new(); // Everyone pays trading_fee_bps
         
         Ok(())
     }
 
-    pub fn create_token_project(
-        ctx: Context<CreateTokenProject>,
-        name: String,
-        symbol: String,
-        initial_price: u64,
-        curve_params: Vec<u64>,
+    pub fn set_referral_fee(
+        ctx: Context<SetReferralFee>,
+        referral_fee_bps: u16,
     ) -> Result<()> {
-        let project = &mut ctx.accounts.project;
-        let mint = &ctx.accounts.mint;
+        let config = &mut ctx.accounts.config;
         
-        // Initialize project data
-        project.mint = mint.key();
-        project.creator = ctx.accounts.authority.key();
-        project.name = name;
-        project.symbol = symbol;
-        project.initial_price = initial_price;
-        project.current_price = initial_price;
-        project.supply = 0;
-        project.reserve_balance = 0;
-        project.curve_params = curve_params;
-        project.is_graduated = false;
-        project.liquidity_pool = None;
-        project.creator_fee_earned = 0;
-        project.platform_fee_earned = 0;
+        // Only the launchpad authority can change the referral share
+        if ctx.accounts.authority.key() != config.authority {
+            return Err(error!(ErrorCode::UnauthorizedAccess));
+        }
         
-        // Initialize the mint with Token-2022 transfer hook
-        initialize_transfer_hook(
-            &ctx.accounts.mint.to_account_info(),
-            &ctx.accounts.authority.to_account_info(),
-            &ctx.program_id,
-            &ctx.accounts.token_program.to_account_info(),
-        )?;
+        // Referral fees come out of the platform's half of the trading fee
+        if referral_fee_bps > MAX_REFERRAL_FEE_BPS {
+            return Err(error!(ErrorCode::InvalidReferralFee));
+        }
+        
+        config.referral_fee_bps = referral_fee_bps;
         
         Ok(())
     }
 
-    pub fn buy_tokens(
-        ctx: Context<BuyTokens>,
-        amount: u64,
+    pub fn set_quote_graduation_cap(
+        ctx: Context<SetQuoteGraduationCap>,
+        graduation_market_cap: u64,
     ) -> Result<()> {
-        let project = &mut ctx.accounts.project;
-        let config = &ctx.accounts.config;
-        
-        // Calculate the price based on the bond curve
-        let price = calculate_buy_price(
-            &project.curve_params,
-            project.supply,
-            amount,
-        )?;
-        
-        // Check if buyer has enough SOL
-        if ctx.accounts.buyer.lamports() < price {
-            return Err(error!(ErrorCode::InsufficientFunds));
+        // Only the launchpad authority can price graduation in a quote token
+        if ctx.accounts.authority.key() != ctx.accounts.config.authority {
+            return Err(error!(ErrorCode::UnauthorizedAccess));
         }
         
-        // Calculate 1% trading fee
-        let total_fee = price
-            .checked_mul(config.trading_fee_bps as u64)
-            .ok_or(error!(ErrorCode::MathOverflow))?
-            .checked_div(10000)
-            .ok_or(error!(ErrorCode::DivisionByZero))?;
-        
-        // Split fee 50/50 between creator and platform
-        let creator_fee = total_fee.checked_div(2).ok_or(error!(ErrorCode::DivisionByZero))?;
-        let platform_fee = total_fee.checked_sub(creator_fee).ok_or(error!(ErrorCode::MathOverflow))?;
-        
-        let reserve_amount = price.checked_sub(total_fee).ok_or(error!(ErrorCode::MathOverflow))?;
-        
-        // Transfer SOL from buyer to project reserve
-        invoke(
-            &system_instruction::transfer(
-                &ctx.accounts.buyer.key(),
-                &project.key(),
-                reserve_amount,
-            ),
-            &[
-                ctx.accounts.buyer.to_account_info(),
-                ctx.accounts.project.to_account_info(),
-                ctx.accounts.system_program.to_account_info(),
-            ],
-        )?;
-        
-        // Transfer creator fee to creator
-        if creator_fee > 0 {
-            invoke(
-                &system_instruction::transfer(
-                    &ctx.accounts.buyer.key(),
-                    &project.creator,
-                    creator_fee,
-                ),
-                &[
-                    ctx.accounts.buyer.to_account_info(),
-                    ctx.accounts.system_program.to_account_info(),
-                ],
-            )?;
-            
-            // Update creator fee earned
-            project.creator_fee_earned = project.creator_fee_earned
-                .checked_add(creator_fee)
-                .ok_or(error!(ErrorCode::MathOverflow))?;
+        if graduation_market_cap == 0 {
+            return Err(error!(ErrorCode::InvalidGraduationCap));
         }
         
-        // Transfer platform fee to fee vault
-        if platform_fee > 0 {
-            invoke(
-                &system_instruction::transfer(
-                    &ctx.accounts.buyer.key(),
-                    &ctx.accounts.fee_vault.key(),
-                    platform_fee,
-                ),
-                &[
-                    ctx.accounts.buyer.to_account_info(),
-                    ctx.accounts.fee_vault.to_account_info(),
-                    ctx.accounts.system_program.to_account_info(),
-                ],
-            )?;
-            
-            // Update platform fee earned
-            project.platform_fee_earned = project.platform_fee_earned
-                .checked_add(platform_fee)
-                .ok_or(error!(ErrorCode::MathOverflow))?;
-        }
+        // Applies to projects launched or migrated from now on, each keeps the cap it started with
+        let quote_config = &mut ctx.accounts.quote_config;
+        quote_config.quote_mint = ctx.accounts.quote_mint.key();
+        quote_config.graduation_market_cap = graduation_market_cap;
         
-        // Mint tokens to buyer
-        // In a real implementation, you would mint tokens to the buyer's token account
-        // For simplicity, we'll just update the project state
-        project.supply = project.supply.checked_add(amount).ok_or(error!(ErrorCode::MathOverflow))?;
-        project.reserve_balance = project.reserve_balance.checked_add(reserve_amount).ok_or(error!(ErrorCode::MathOverflow))?;
-        project.current_price = calculate_current_price(&project.curve_params, project.supply)?;
+        Ok(())
+    }
+
+    pub fn set_fee_tiers(
+        ctx: Context<SetFeeTiers>,
+        fee_tiers: Vec<FeeTier>,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
         
-        // Check if the token is eligible for graduation
-        if !project.is_graduated && is_eligible_for_graduation(project.supply, project.current_price, config.graduation_market_cap)? {
-            // Mark as eligible for graduation
-            // In a real implementation, you might want to emit an event or set a flag
-            msg!("Token is now eligible for graduation!");
+        // Only the launchpad authority can change the fee schedule
+        if ctx.accounts.authority.key() != config.authority {
+            return Err(error!(ErrorCode::UnauthorizedAccess));
         }
         
-        // Update bundle status if bundle tracker is provided
-        if let Some(bundle_tracker) = &mut ctx.accounts.bundle_tracker {
-            // Get the buyer's token balance after this purchase
-            let new_balance = ctx.accounts.buyer_token_account.amount.checked_add(amount)
-                .ok_or(error!(ErrorCode::MathOverflow))?;
-                
-            // For simplicity, we'll just update with the current wallet's balance
-            // In a real implementation, you would track all related wallets
-            let related_wallets = Vec::new();
-            
-            // Update the bundle tracker
-            update_bundle_tracker(
-                bundle_tracker,
-                &ctx.accounts.buyer.key(),
-                &project.mint,
-                related_wallets,
-                new_balance,
-                config.bundle_threshold_percentage,
-                project.supply
-            )?;
-        }
+        validate_fee_tiers(&fee_tiers, config.trading_fee_bps)?;
+        
+        config.fee_tiers = fee_tiers;
         
         Ok(())
     }
 
-    pub fn sell_tokens(
-        ctx: Context<SellTokens>,
-        amount: u64,
+    pub fn register_referrer(
+        ctx: Context<RegisterReferrer>,
     ) -> Result<()> {
-        let project = &mut ctx.accounts.project;
-        let config = &ctx.accounts.config;
+        let referrer = &mut ctx.accounts.referrer;
         
-        // Check if the project has enough supply
-        if project.supply < amount {
-            return Err(error!(ErrorCode::InsufficientSupply));
-        }
+        referrer.wallet = ctx.accounts.wallet.key();
+        referrer.total_volume = 0;
+        referrer.total_fees_earned = 0;
+        referrer.fees_claimable = 0;
+        referrer.last_updated = Clock::get()?.unix_timestamp;
         
-        // Calculate the sell price based on the bond curve
-        let price = calculate_sell_price(
-            &project.curve_params,
-            project.supply,
-            amount,
-        )?;
+        Ok(())
+    }
+
+    pub fn claim_referral_fees(
+        ctx: Context<ClaimReferralFees>,
+    ) -> Result<()> {
+        let referrer = &mut ctx.accounts.referrer;
+        let amount = referrer.fees_claimable;
         
-        // Check if the project has enough reserve balance
-        if project.reserve_balance < price {
-            return Err(error!(ErrorCode::InsufficientFunds));
+        if amount == 0 {
+            return Err(error!(ErrorCode::NothingToClaim));
         }
         
-        // Calculate 1% trading fee
-        let total_fee = price
-            .checked_mul(config.trading_fee_bps as u64)
-            .ok_or(error!(ErrorCode::MathOverflow))?
-            .checked_div(10000)
-            .ok_or(error!(ErrorCode::DivisionByZero))?;
-        
-        // Split fee 50/50 between creator and platform
-        let creator_fee = total_fee.checked_div(2).ok_or(error!(ErrorCode::DivisionByZero))?;
-        let platform_fee = total_fee.checked_sub(creator_fee).ok_or(error!(ErrorCode::MathOverflow))?;
-        
-        let payout_amount = price.checked_sub(total_fee).ok_or(error!(ErrorCode::MathOverflow))?;
-        
-        // Transfer SOL from project reserve to seller
-        **project.to_account_info().try_borrow_mut_lamports()? = project
+        // Referral fees are held as lamports on the referrer account, on top of its rent
+        **referrer.to_account_info().try_borrow_mut_lamports()? = referrer
             .to_account_info()
             .lamports()
-            .checked_sub(payout_amount)
+            .checked_sub(amount)
             .ok_or(error!(ErrorCode::InsufficientFunds))?;
             
-        **ctx.accounts.seller.try_borrow_mut_lamports()? = ctx
-            .accounts.seller
+        **ctx.accounts.wallet.try_borrow_mut_lamports()? = ctx
+            .accounts.wallet
             .lamports()
-            .checked_add(payout_amount)
+            .checked_add(amount)
             .ok_or(error!(ErrorCode::MathOverflow))?;
         
-        // Transfer creator fee
-        if creator_fee > 0 {
-            **project.to_account_info().try_borrow_mut_lamports()? = project
-                .to_account_info()
-                .lamports()
-                .checked_sub(creator_fee)
-                .ok_or(error!(ErrorCode::InsufficientFunds))?;
-                
-            // In a real implementation, you would transfer to the creator
-            // For simplicity, we'll just update the project state
-            project.creator_fee_earned = project.creator_fee_earned
-                .checked_add(creator_fee)
-                .ok_or(error!(ErrorCode::MathOverflow))?;
-        }
+        referrer.fees_claimable = 0;
+        referrer.last_updated = Clock::get()?.unix_timestamp;
         
-        // Transfer platform fee
-        if platform_fee > 0 {
-            **project.to_account_info().try_borrow_mut_lamports()? = project
-                .to_account_info()
-                .lamports()
-                .checked_sub(platform_fee)
-                .ok_or(error!(ErrorCode::InsufficientFunds))?;
-                
-            **ctx.accounts.fee_vault.try_borrow_mut_lamports()? = ctx
-                .accounts.fee_vault
-                .lamports()
-                .checked_add(platform_fee)
-                .ok_or(error!(ErrorCode::MathOverflow))?;
-                
-            project.platform_fee_earned = project.platform_fee_earned
-                .checked_add(platform_fee)
-                .ok_or(error!(ErrorCode::MathOverflow))?;
-        }
+        Ok(())
+    }
+
+    pub fn migrate_config(
+        ctx: Context<MigrateConfig>,
+    ) -> Result<()> {
+        let config_info = ctx.accounts.config.to_account_info();
         
-        // Burn tokens from seller
-        // In a real implementation, you would burn tokens from the seller's token account
-        // For simplicity, we'll just update the project state
-        project.supply = project.supply.checked_sub(amount).ok_or(error!(ErrorCode::MathOverflow))?;
-        project.reserve_balance = project.reserve_balance.checked_sub(price).ok_or(error!(ErrorCode::MathOverflow))?;
-        project.current_price = calculate_current_price(&project.curve_params, project.supply)?;
+        let config = upgrade_config(&config_info.try_borrow_data()?)?
+            .ok_or(error!(ErrorCode::AccountAlreadyMigrated))?;
         
-        // Update bundle status if bundle tracker is provided
-        if let Some(bundle_tracker) = &mut ctx.accounts.bundle_tracker {
-            // Get the seller's token balance after this sale
-            let new_balance = ctx.accounts.seller_token_account.amount.checked_sub(amount)
-                .ok_or(error!(ErrorCode::MathOverflow))?;
-                
-            // For simplicity, we'll just update with the current wallet's balance
-            // In a real implementation, you would track all related wallets
-            let related_wallets = Vec::new();
-            
-            // Update the bundle tracker
-            update_bundle_tracker(
-                bundle_tracker,
-                &ctx.accounts.seller.key(),
-                &project.mint,
-                related_wallets,
-                new_balance,
-                config.bundle_threshold_percentage,
-                project.supply
-            )?;
+        // Only the launchpad authority can migrate the config
+        if ctx.accounts.authority.key() != config.authority {
+            return Err(error!(ErrorCode::UnauthorizedAccess));
         }
         
-        Ok(())
+        write_migrated_account(
+            &config_info,
+            &ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &config,
+            8 + LaunchpadConfig::INIT_SPACE,
+        )
     }
 
-    pub fn register_wallet_relationship(
-        ctx: Context<RegisterWalletRelationship>,
-        mint: Pubkey,
-        wallet_a: Pubkey,
-        wallet_b: Pubkey,
-        relationship_strength: u16,
-        transaction_count: u16,
+    pub fn migrate_project(
+        ctx: Context<MigrateProject>,
     ) -> Result<()> {
-        let relationship = &mut ctx.accounts.relationship;
-        
-        // Only the launchpad authority can register wallet relationships
+        // Only the launchpad authority can migrate projects
         if ctx.accounts.authority.key() != ctx.accounts.config.authority {
             return Err(error!(ErrorCode::UnauthorizedAccess));
         }
         
-        relationship.mint = mint;
-        relationship.wallet_a = wallet_a;
-        relationship.wallet_b = wallet_b;
-        relationship.relationship_strength = relationship_strength;
-        relationship.last_transaction = Clock::get()?.unix_timestamp;
-        relationship.transaction_count = transaction_count;
+        let project_info = ctx.accounts.project.to_account_info();
         
-        Ok(())
+        let graduation_market_cap = ctx.accounts.config.graduation_market_cap;
+        let quote_config = ctx.accounts.quote_config.as_deref();
+        
+        let project = upgrade_project(&project_info.try_borrow_data()?, graduation_market_cap, quote_config)?
+            .ok_or(error!(ErrorCode::AccountAlreadyMigrated))?;
+        
+        // Every buy records into the launch ledger, so projects that predate it get one
+        // here, along with the sniper cluster their launch window tags buyers into
+        let mint = ctx.accounts.mint.key();
+        if ctx.accounts.launch_ledger.mint == Pubkey::default() {
+            init_launch_ledger(&mut ctx.accounts.launch_ledger, mint, project.launch_slot);
+        }
+        if ctx.accounts.sniper_cluster.mint == Pubkey::default() {
+            init_sniper_cluster(&mut ctx.accounts.sniper_cluster, mint, ctx.accounts.config.snipe_block, Clock::get()?.unix_timestamp);
+        }
+        
+        write_migrated_account(
+            &project_info,
+            &ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &project,
+            8 + TokenProject::INIT_SPACE,
+        )
     }
 
-    pub fn update_bundle_status(
-        ctx: Context<UpdateBundleStatus>,
-        mint: Pubkey,
-        wallet: Pubkey,
-        related_wallets: Vec<Pubkey>,
-        total_bundle_balance: u64,
+    pub fn set_snipe_rules(
+        ctx: Context<SetSnipeRules>,
+        snipe_window_slots: u64,
+        snipe_tax_bps: u16,
+        snipe_block: bool,
     ) -> Result<()> {
-        let bundle_tracker = &mut ctx.accounts.bundle_tracker;
-        let config = &ctx.accounts.config;
-        let project = &ctx.accounts.project;
+        let config = &mut ctx.accounts.config;
         
-        // Only the launchpad authority can update bundle status
+        // Only the launchpad authority can change the sniping rules
         if ctx.accounts.authority.key() != config.authority {
             return Err(error!(ErrorCode::UnauthorizedAccess));
         }
         
-        // Update the bundle tracker
-        update_bundle_tracker(
-            bundle_tracker,
-            &wallet,
-            &mint,
-            related_wallets,
-            total_bundle_balance,
-            config.bundle_threshold_percentage,
-            project.supply
-        )?;
+        // The launch ledger only covers a fixed number of slots
+        if snipe_window_slots > LAUNCH_LEDGER_SLOTS as u64 || snipe_tax_bps > 10000 {
+            return Err(error!(ErrorCode::InvalidSnipeRules));
+        }
+        
+        config.snipe_window_slots = snipe_window_slots;
+        config.snipe_tax_bps = snipe_tax_bps;
+        config.snipe_block = snipe_block;
         
         Ok(())
     }
 
-    pub fn graduate_token(
-        ctx: Context<GraduateToken>,
-        mint: Pubkey,
-        liquidity_pool: Pubkey,
+    pub fn set_bundle_flag_duration(
+        ctx: Context<SetBundleFlagDuration>,
+        bundle_flag_duration: i64,
     ) -> Result<()> {
-        let project = &mut ctx.accounts.project;
-        let config = &ctx.accounts.config;
+        let config = &mut ctx.accounts.config;
         
-        // Only the launchpad authority can graduate tokens
+        // Only the launchpad authority can change how long flags last
         if ctx.accounts.authority.key() != config.authority {
             return Err(error!(ErrorCode::UnauthorizedAccess));
         }
         
-        // Check if the token is eligible for graduation
-        if !is_eligible_for_graduation(project.supply, project.current_price, config.graduation_market_cap)? {
-            return Err(error!(ErrorCode::TokenNotEligibleForGraduation));
+        // Zero means flags never expire on their own
+        if bundle_flag_duration < 0 {
+            return Err(error!(ErrorCode::InvalidBundleFlagDuration));
         }
         
-        // Update project state
-        project.is_graduated = true;
-        project.liquidity_pool = Some(liquidity_pool);
+        config.bundle_flag_duration = bundle_flag_duration;
         
-        // In a real implementation, you would:
-        // 1. Create a liquidity pool on Raydium
-        // 2. Transfer tokens and SOL to the pool
-        // 3. Initialize the pool
+        Ok(())
+    }
+
+    pub fn set_relationship_params(
+        ctx: Context<SetRelationshipParams>,
+        relationship_threshold: u16,
+        funding_window_slots: u64,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        
+        // Only the launchpad authority can tune relationship inference
+        if ctx.accounts.authority.key() != config.authority {
+            return Err(error!(ErrorCode::UnauthorizedAccess));
+        }
+        
+        config.relationship_threshold = relationship_threshold;
+        config.funding_window_slots = funding_window_slots;
         
         Ok(())
     }
 
-    pub fn create_raydium_pool(
-        ctx: Context<CreateRaydiumPool>,
-        mint: Pubkey,
-        initial_liquidity_amount: u64,
-        initial_token_amount: u64,
+    pub fn set_bundle_penalty_mode(
+        ctx: Context<SetBundlePenaltyMode>,
+        mode: BundlePenaltyMode,
     ) -> Result<()> {
-        let project = &mut ctx.accounts.project;
-        let config = &ctx.accounts.config;
+        let config = &mut ctx.accounts.config;
         
-        // Only the launchpad authority can create liquidity pools
+        // Only the launchpad authority can change the penalty policy
         if ctx.accounts.authority.key() != config.authority {
             return Err(error!(ErrorCode::UnauthorizedAccess));
         }
         
-        // Check if the token is eligible for graduation
-        if !is_eligible_for_graduation(project.supply, project.current_price, config.graduation_market_cap)? {
-            return Err(error!(ErrorCode::TokenNotEligibleForGraduation));
+        config.bundle_penalty_mode = mode;
+        
+        Ok(())
+    }
+
+    pub fn create_token_project(
+        ctx: Context<CreateTokenProject>,
+        name: String,
+        symbol: String,
+        initial_price: u64,
+        curve_params: Vec<u64>,
+        max_wallet_bps: Option<u16>,
+        max_buy_per_tx: Option<u64>,
+        buy_cooldown_secs: Option<u32>,
+        uri: String,
+        extra_metadata: Vec<MetadataField>,
+        decimals: u8,
+        initial_buy_amount: Option<u64>,
+        max_sol_cost: Option<u64>,
+        vesting: Option<VestingSchedule>,
+        launch_at: Option<i64>,
+        whitelist: Option<WhitelistPhase>,
+        launch_fee: Option<LaunchFeeCurve>,
+        auction_ends_at: Option<i64>,
+        min_raise: Option<MinRaise>,
+    ) -> Result<()> {
+        // The project account is sized for these caps
+        if name.len() > MAX_NAME_LEN {
+            return Err(error!(ErrorCode::NameTooLong));
+        }
+        if symbol.len() > MAX_SYMBOL_LEN {
+            return Err(error!(ErrorCode::SymbolTooLong));
+        }
+        if curve_params.len() > MAX_CURVE_PARAMS {
+            return Err(error!(ErrorCode::TooManyCurveParams));
         }
         
-        // Check if the project has enough reserve balance for initial liquidity
-        if project.reserve_balance < initial_liquidity_amount {
-            return Err(error!(ErrorCode::InsufficientFunds));
+        validate_metadata(&uri, &extra_metadata)?;
+        
+        if matches!(max_wallet_bps, Some(bps) if bps == 0 || bps > 10000) {
+            return Err(error!(ErrorCode::InvalidTradingLimits));
         }
         
-        // In a real implementation, you would:
-        // 1. Create a Raydium CLMM pool
-        // 2. Transfer SOL and tokens to the pool
-        // 3. Initialize the pool with the desired parameters
+        // Prices are given in billionths of a quote token, as lamports are for SOL, and
+        // stored in base units of the quote token
+        let quote_decimals = ctx.accounts.quote_mint
+            .as_ref()
+            .map(|quote_mint| quote_mint.decimals)
+            .unwrap_or(SOL_DECIMALS);
+        let initial_price = scale_to_quote_decimals(initial_price, quote_decimals)?;
+        let curve_params = normalize_curve_params(curve_params, quote_decimals)?;
         
-        // For now, we'll just update the project state
-        project.is_graduated = true;
-        project.liquidity_pool = Some(ctx.accounts.liquidity_pool.key());
+        // Graduation is measured in the project's own quote: the launchpad's cap for SOL,
+        // the cap configured for the quote mint otherwise
+        let graduation_market_cap = match &ctx.accounts.quote_mint {
+            Some(quote_mint) => quote_graduation_market_cap(ctx.accounts.quote_config.as_deref(), &quote_mint.key())?,
+            None => ctx.accounts.config.graduation_market_cap,
+        };
         
-        // Transfer SOL from project reserve to liquidity pool
-        **project.to_account_info().try_borrow_mut_lamports()? = project
-            .to_account_info()
-            .lamports()
-            .checked_sub(initial_liquidity_amount)
-            .ok_or(error!(ErrorCode::InsufficientFunds))?;
+        if let Some(vesting) = &vesting {
+            let max_premint = max_premint_supply(initial_price, graduation_market_cap)?;
+            validate_vesting_schedule(vesting, max_premint)?;
+        }
+        
+        if let Some(launch_fee) = &launch_fee {
+            validate_launch_fee(launch_fee)?;
+        }
+        
+        let now = Clock::get()?.unix_timestamp;
+        
+        // The auction account is only created for, and required by, an auction launch
+        if ctx.accounts.launch_auction.is_some() != auction_ends_at.is_some() {
+            return Err(error!(ErrorCode::LaunchAuctionRequired));
+        }
+        
+        // A batch auction replaces the scheduled and whitelist openings
+        if let Some(auction_ends_at) = auction_ends_at {
+            if auction_ends_at <= now || launch_at.is_some() || whitelist.is_some() {
+                return Err(error!(ErrorCode::InvalidLaunchSchedule));
+            }
+            if ctx.accounts.quote_mint.is_some() {
+                return Err(error!(ErrorCode::UnsupportedAuctionQuote));
+            }
+        }
+        
+        // The creator commits to an auction like everyone else
+        validate_auction_dev_buy(auction_ends_at, initial_buy_amount)?;
+        
+        // Trading opens immediately unless a later start time is given
+        let launch_at = launch_at.unwrap_or(now);
+        validate_launch_schedule(launch_at, &whitelist)?;
+        
+        // Refunds are paid from the SOL reserve, and a vesting allocation never paid
+        // into it, so refundable launches support neither
+        if let Some(min_raise) = &min_raise {
+            let opens_at = auction_ends_at
+                .or(whitelist.as_ref().map(|whitelist| whitelist.public_open_at))
+                .unwrap_or(launch_at);
+            validate_min_raise(min_raise, opens_at)?;
             
-        **ctx.accounts.liquidity_pool.try_borrow_mut_lamports()? = ctx
-            .accounts.liquidity_pool
-            .lamports()
-            .checked_add(initial_liquidity_amount)
+            if vesting.is_some() {
+                return Err(error!(ErrorCode::InvalidMinRaise));
+            }
+            if ctx.accounts.quote_mint.is_some() {
+                return Err(error!(ErrorCode::UnsupportedRefundQuote));
+            }
+        }
+        
+        let project_key = ctx.accounts.project.key();
+        let project = &mut ctx.accounts.project;
+        let mint = &ctx.accounts.mint;
+        
+        // Initialize project data
+        project.mint = mint.key();
+        project.creator = ctx.accounts.authority.key();
+        project.name = name.clone();
+        project.symbol = symbol.clone();
+        project.initial_price = initial_price;
+        project.current_price = initial_price;
+        project.supply = 0;
+        project.reserve_balance = 0;
+        project.curve_params = curve_params;
+        project.is_graduated = false;
+        project.liquidity_pool = None;
+        project.creator_fee_earned = 0;
+        project.platform_fee_earned = 0;
+        project.max_wallet_bps = max_wallet_bps;
+        project.max_buy_per_tx = max_buy_per_tx;
+        project.buy_cooldown_secs = buy_cooldown_secs;
+        project.version = PROJECT_VERSION;
+        project.graduation_market_cap = graduation_market_cap;
+        
+        // Projects are priced in native SOL unless a quote mint is passed
+        match &ctx.accounts.quote_mint {
+            Some(quote_mint) => {
+                if ctx.accounts.quote_vault.is_none() {
+                    return Err(error!(ErrorCode::QuoteAccountsRequired));
+                }
+                project.quote_mint = Some(quote_mint.key());
+                project.quote_decimals = quote_mint.decimals;
+            }
+            None => {
+                project.quote_mint = None;
+                project.quote_decimals = SOL_DECIMALS;
+            }
+        }
+        project.creator_allocation = 0;
+        project.premint_supply = 0;
+        project.launch_at = launch_at;
+        project.public_open_at = whitelist.as_ref().map(|whitelist| whitelist.public_open_at).unwrap_or(launch_at);
+        project.whitelist_root = whitelist.as_ref().map(|whitelist| whitelist.merkle_root);
+        project.whitelist_cap = whitelist.as_ref().map(|whitelist| whitelist.wallet_cap).unwrap_or(0);
+        project.launch_fee = launch_fee;
+        project.min_raise = min_raise.as_ref().map(|min_raise| min_raise.amount);
+        project.deadline = min_raise.as_ref().map(|min_raise| min_raise.deadline).unwrap_or(0);
+        project.min_raise_met = false;
+        project.has_third_party_buys = false;
+        project.is_cancelled = false;
+        
+        // Auctioned projects stay closed to continuous trading until settle_launch
+        if auction_ends_at.is_some() {
+            project.launch_at = i64::MAX;
+            project.public_open_at = i64::MAX;
+        }
+        
+        // The sniping window of a scheduled launch opens with its first public buy
+        project.launch_slot = if project.public_open_at > now {
+            SCHEDULED_LAUNCH_SLOT
+        } else {
+            Clock::get()?.slot
+        };
+        
+        // Start the per-slot buy ledger for the launch window
+        init_launch_ledger(&mut ctx.accounts.launch_ledger, project.mint, project.launch_slot);
+        
+        // Wallets that buy inside the sniping window are tagged into this cluster
+        init_sniper_cluster(&mut ctx.accounts.sniper_cluster, project.mint, ctx.accounts.config.snipe_block, now);
+        
+        // Commitments are held on the auction account until it settles
+        if let Some(auction_ends_at) = auction_ends_at {
+            let launch_auction = ctx.accounts.launch_auction
+                .as_mut()
+                .ok_or(error!(ErrorCode::LaunchAuctionRequired))?;
+            launch_auction.mint = project.mint;
+            launch_auction.ends_at = auction_ends_at;
+            launch_auction.total_committed = 0;
+            launch_auction.commitment_count = 0;
+            launch_auction.settled = false;
+            launch_auction.clearing_tokens = 0;
+            launch_auction.refund_pool = 0;
+            launch_auction.distributed_tokens = 0;
+            launch_auction.distributed_refund = 0;
+        }
+        
+        // Create the Token-2022 mint with the project PDA as its authorities
+        create_token_mint(
+            &ctx.accounts.mint.to_account_info(),
+            &ctx.accounts.authority.to_account_info(),
+            &project_key,
+            ctx.program_id,
+            decimals,
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        
+        // Keep the metadata on the mint itself, updatable only through the project PDA
+        let mint_key = ctx.accounts.mint.key();
+        let project_seeds: &[&[u8]] = &[b"project", mint_key.as_ref(), &[ctx.bumps.project]];
+        
+        initialize_token_metadata(
+            &ctx.accounts.mint.to_account_info(),
+            &ctx.accounts.project.to_account_info(),
+            &ctx.accounts.project.to_account_info(),
+            &ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            name,
+            symbol,
+            uri,
+            &extra_metadata,
+            &[project_seeds],
+        )?;
+        
+        // Mint the creator's locked allocation to the vesting escrow. It sits outside
+        // the curve, so it counts toward market cap but not toward curve pricing.
+        if let Some(vesting) = &vesting {
+            let (vesting_escrow, vesting_vault, associated_token_program) = match (
+                ctx.accounts.vesting_escrow.as_mut(),
+                &ctx.accounts.vesting_vault,
+                &ctx.accounts.associated_token_program,
+            ) {
+                (Some(vesting_escrow), Some(vesting_vault), Some(associated_token_program)) => {
+                    (vesting_escrow, vesting_vault, associated_token_program)
+                }
+                _ => return Err(error!(ErrorCode::VestingAccountsRequired)),
+            };
+            
+            initialize_vesting_escrow(
+                vesting_escrow,
+                &mint_key,
+                &ctx.accounts.authority.key(),
+                vesting,
+                Clock::get()?.unix_timestamp,
+            )?;
+            
+            anchor_spl::associated_token::create(
+                CpiContext::new(
+                    associated_token_program.to_account_info(),
+                    anchor_spl::associated_token::Create {
+                        payer: ctx.accounts.authority.to_account_info(),
+                        associated_token: vesting_vault.to_account_info(),
+                        authority: vesting_escrow.to_account_info(),
+                        mint: ctx.accounts.mint.to_account_info(),
+                        system_program: ctx.accounts.system_program.to_account_info(),
+                        token_program: ctx.accounts.token_program.to_account_info(),
+                    },
+                ),
+            )?;
+            
+            token_2022::mint_to(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    MintTo {
+                        mint: ctx.accounts.mint.to_account_info(),
+                        to: vesting_vault.to_account_info(),
+                        authority: ctx.accounts.project.to_account_info(),
+                    },
+                    &[project_seeds],
+                ),
+                vesting.amount,
+            )?;
+            
+            ctx.accounts.project.premint_supply = vesting.amount;
+        }
+        
+        // The creator's dev-buy is the first curve purchase, made in the same
+        // instruction so nobody can trade ahead of it
+        if let Some(initial_buy_amount) = initial_buy_amount.filter(|amount| *amount > 0) {
+            let project = &mut ctx.accounts.project;
+            
+            if project.quote_mint.is_some() {
+                return Err(error!(ErrorCode::UnsupportedDevBuyQuote));
+            }
+            
+            let (fee_vault, creator_token_account, associated_token_program) = match (
+                &ctx.accounts.fee_vault,
+                &ctx.accounts.creator_token_account,
+                &ctx.accounts.associated_token_program,
+            ) {
+                (Some(fee_vault), Some(creator_token_account), Some(associated_token_program)) => {
+                    (fee_vault, creator_token_account, associated_token_program)
+                }
+                _ => return Err(error!(ErrorCode::DevBuyAccountsRequired)),
+            };
+            
+            check_max_buy(project, initial_buy_amount)?;
+            
+            let price = calculate_buy_price(&project.curve_params, project.supply, initial_buy_amount)?;
+            let total_fee = price
+                .checked_mul(ctx.accounts.config.trading_fee_bps as u64)
+                .ok_or(error!(ErrorCode::MathOverflow))?
+                .checked_div(10000)
+                .ok_or(error!(ErrorCode::DivisionByZero))?;
+            
+            // The creator's half of the fee would be paid back to the creator, so only
+            // the platform half is charged, on top of the curve price
+            let creator_fee = total_fee.checked_div(2).ok_or(error!(ErrorCode::DivisionByZero))?;
+            let platform_fee = total_fee.checked_sub(creator_fee).ok_or(error!(ErrorCode::MathOverflow))?;
+            let reserve_amount = price;
+            let sol_cost = reserve_amount.checked_add(platform_fee).ok_or(error!(ErrorCode::MathOverflow))?;
+            
+            if matches!(max_sol_cost, Some(max_sol_cost) if sol_cost > max_sol_cost) {
+                return Err(error!(ErrorCode::SlippageExceeded));
+            }
+            
+            invoke(
+                &system_instruction::transfer(
+                    &ctx.accounts.authority.key(),
+                    &project.key(),
+                    reserve_amount,
+                ),
+                &[
+                    ctx.accounts.authority.to_account_info(),
+                    project.to_account_info(),
+                    ctx.accounts.system_program.to_account_info(),
+                ],
+            )?;
+            
+            if platform_fee > 0 {
+                invoke(
+                    &system_instruction::transfer(
+                        &ctx.accounts.authority.key(),
+                        &fee_vault.key(),
+                        platform_fee,
+                    ),
+                    &[
+                        ctx.accounts.authority.to_account_info(),
+                        fee_vault.to_account_info(),
+                        ctx.accounts.system_program.to_account_info(),
+                    ],
+                )?;
+            }
+            
+            // The creator's token account can only be created once the mint exists
+            anchor_spl::associated_token::create(
+                CpiContext::new(
+                    associated_token_program.to_account_info(),
+                    anchor_spl::associated_token::Create {
+                        payer: ctx.accounts.authority.to_account_info(),
+                        associated_token: creator_token_account.to_account_info(),
+                        authority: ctx.accounts.authority.to_account_info(),
+                        mint: ctx.accounts.mint.to_account_info(),
+                        system_program: ctx.accounts.system_program.to_account_info(),
+                        token_program: ctx.accounts.token_program.to_account_info(),
+                    },
+                ),
+            )?;
+            
+            token_2022::mint_to(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    MintTo {
+                        mint: ctx.accounts.mint.to_account_info(),
+                        to: creator_token_account.to_account_info(),
+                        authority: project.to_account_info(),
+                    },
+                    &[project_seeds],
+                ),
+                initial_buy_amount,
+            )?;
+            
+            project.supply = project.supply.checked_add(initial_buy_amount).ok_or(error!(ErrorCode::MathOverflow))?;
+            project.reserve_balance = project.reserve_balance.checked_add(reserve_amount).ok_or(error!(ErrorCode::MathOverflow))?;
+            project.current_price = calculate_current_price(&project.curve_params, project.supply)?;
+            project.platform_fee_earned = project.platform_fee_earned
+                .checked_add(platform_fee)
+                .ok_or(error!(ErrorCode::MathOverflow))?;
+            record_raise_progress(project);
+            
+            check_max_wallet(project, initial_buy_amount)?;
+            
+            // Recorded as a disclosed allocation rather than a launch-window buy, so it
+            // is neither snipe-taxed nor counted toward the creator's bundled holdings.
+            // The creator's token account was just created, so the dev-buy is all it holds.
+            project.creator_allocation = initial_buy_amount;
+            
+            emit!(CreatorAllocationRecorded {
+                mint: project.mint,
+                creator: project.creator,
+                amount: initial_buy_amount,
+                sol_cost,
+                timestamp: Clock::get()?.unix_timestamp,
+            });
+        }
+        
+        Ok(())
+    }
+
+    pub fn initialize_extra_account_meta_list(
+        ctx: Context<InitializeExtraAccountMetaList>,
+    ) -> Result<()> {
+        // Token-2022 resolves the hook's extra accounts from this list on every transfer
+        write_extra_account_metas(&ctx.accounts.extra_account_meta_list.to_account_info())
+    }
+
+    /// Token-2022 calls the transfer hook interface's Execute instruction, which has no
+    /// Anchor discriminator, so it lands here
+    pub fn fallback<'info>(
+        program_id: &Pubkey,
+        accounts: &'info [AccountInfo<'info>],
+        data: &[u8],
+    ) -> Result<()> {
+        process_transfer_hook(program_id, accounts, data)
+    }
+
+    pub fn update_project_metadata(
+        ctx: Context<UpdateProjectMetadata>,
+        name: Option<String>,
+        symbol: Option<String>,
+        uri: Option<String>,
+        extra_metadata: Vec<MetadataField>,
+    ) -> Result<()> {
+        let project = &mut ctx.accounts.project;
+        
+        // Only the creator can change metadata, and only until graduation
+        if ctx.accounts.creator.key() != project.creator {
+            return Err(error!(ErrorCode::UnauthorizedAccess));
+        }
+        if project.is_graduated {
+            return Err(error!(ErrorCode::MetadataLocked));
+        }
+        
+        if matches!(&name, Some(name) if name.len() > MAX_NAME_LEN) {
+            return Err(error!(ErrorCode::NameTooLong));
+        }
+        if matches!(&symbol, Some(symbol) if symbol.len() > MAX_SYMBOL_LEN) {
+            return Err(error!(ErrorCode::SymbolTooLong));
+        }
+        validate_metadata(uri.as_deref().unwrap_or_default(), &extra_metadata)?;
+        
+        // Mirror name and symbol on the project account
+        if let Some(name) = &name {
+            project.name = name.clone();
+        }
+        if let Some(symbol) = &symbol {
+            project.symbol = symbol.clone();
+        }
+        
+        let mint_key = ctx.accounts.mint.key();
+        let project_seeds: &[&[u8]] = &[b"project", mint_key.as_ref(), &[ctx.bumps.project]];
+        
+        let mut updates: Vec<(Field, String)> = Vec::new();
+        if let Some(name) = name {
+            updates.push((Field::Name, name));
+        }
+        if let Some(symbol) = symbol {
+            updates.push((Field::Symbol, symbol));
+        }
+        if let Some(uri) = uri {
+            updates.push((Field::Uri, uri));
+        }
+        for field in extra_metadata {
+            updates.push((Field::Key(field.key), field.value));
+        }
+        
+        for (field, value) in updates {
+            set_metadata_field(
+                &ctx.accounts.mint.to_account_info(),
+                &ctx.accounts.project.to_account_info(),
+                &ctx.accounts.creator.to_account_info(),
+                &ctx.accounts.token_program.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+                field,
+                value,
+                &[project_seeds],
+            )?;
+        }
+        
+        Ok(())
+    }
+
+    pub fn claim_vested(
+        ctx: Context<ClaimVested>,
+    ) -> Result<()> {
+        let vesting_escrow = &ctx.accounts.vesting_escrow;
+        
+        if ctx.accounts.beneficiary.key() != vesting_escrow.beneficiary {
+            return Err(error!(ErrorCode::UnauthorizedAccess));
+        }
+        
+        let now = Clock::get()?.unix_timestamp;
+        let amount = claimable_amount(vesting_escrow, now)?;
+        if amount == 0 {
+            return Err(error!(ErrorCode::NothingToClaim));
+        }
+        
+        // The mint's transfer hook is this program and cannot be re-entered, so vested
+        // tokens are burned from the escrow vault and minted to the beneficiary instead
+        // of transferred. Mint supply is unchanged.
+        let mint_key = ctx.accounts.mint.key();
+        let escrow_seeds: &[&[u8]] = &[b"vesting", mint_key.as_ref(), &[ctx.bumps.vesting_escrow]];
+        let project_seeds: &[&[u8]] = &[b"project", mint_key.as_ref(), &[ctx.bumps.project]];
+        
+        token_2022::burn(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    from: ctx.accounts.vesting_vault.to_account_info(),
+                    authority: ctx.accounts.vesting_escrow.to_account_info(),
+                },
+                &[escrow_seeds],
+            ),
+            amount,
+        )?;
+        
+        token_2022::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.beneficiary_token_account.to_account_info(),
+                    authority: ctx.accounts.project.to_account_info(),
+                },
+                &[project_seeds],
+            ),
+            amount,
+        )?;
+        
+        let vesting_escrow = &mut ctx.accounts.vesting_escrow;
+        vesting_escrow.claimed_amount = vesting_escrow.claimed_amount
+            .checked_add(amount)
             .ok_or(error!(ErrorCode::MathOverflow))?;
         
-        // Update project state
-        project.reserve_balance = project.reserve_balance
-            .checked_sub(initial_liquidity_amount)
+        // Unlocked tokens join the creator's disclosed allocation, capped at what the
+        // creator holds once they arrive. No hook runs on a mint, so this is the only
+        // write to the project in this instruction.
+        ctx.accounts.beneficiary_token_account.reload()?;
+        let project = &mut ctx.accounts.project;
+        project.creator_allocation = project.creator_allocation
+            .checked_add(amount)
             .ok_or(error!(ErrorCode::MathOverflow))?;
+        cap_creator_allocation(project, &ctx.accounts.beneficiary.key(), ctx.accounts.beneficiary_token_account.amount);
+        
+        emit!(VestedTokensClaimed {
+            mint: mint_key,
+            beneficiary: vesting_escrow.beneficiary,
+            amount,
+            claimed_amount: vesting_escrow.claimed_amount,
+            total_amount: vesting_escrow.total_amount,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    pub fn commit_to_launch(
+        ctx: Context<CommitToLaunch>,
+        amount: u64,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        
+        if ctx.accounts.project.is_cancelled {
+            return Err(error!(ErrorCode::ProjectCancelled));
+        }
+        if ctx.accounts.launch_auction.settled || now >= ctx.accounts.launch_auction.ends_at {
+            return Err(error!(ErrorCode::AuctionClosed));
+        }
+        if amount == 0 {
+            return Err(error!(ErrorCode::InvalidCommitment));
+        }
+        
+        // Committed SOL is escrowed on the auction account itself
+        invoke(
+            &system_instruction::transfer(
+                &ctx.accounts.buyer.key(),
+                &ctx.accounts.launch_auction.key(),
+                amount,
+            ),
+            &[
+                ctx.accounts.buyer.to_account_info(),
+                ctx.accounts.launch_auction.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+        )?;
+        
+        record_commitment(
+            &mut ctx.accounts.launch_auction,
+            &mut ctx.accounts.commitment,
+            &ctx.accounts.buyer.key(),
+            amount,
+        )?;
+        
+        // Commitments from anyone but the creator rule out an early cancellation
+        if ctx.accounts.buyer.key() != ctx.accounts.project.creator {
+            ctx.accounts.project.has_third_party_buys = true;
+        }
+        
+        emit!(LaunchCommitted {
+            mint: ctx.accounts.launch_auction.mint,
+            wallet: ctx.accounts.buyer.key(),
+            amount,
+            wallet_committed: ctx.accounts.commitment.amount,
+            total_committed: ctx.accounts.launch_auction.total_committed,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    pub fn settle_launch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SettleLaunch<'info>>,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        
+        if now < ctx.accounts.launch_auction.ends_at {
+            return Err(error!(ErrorCode::AuctionNotEnded));
+        }
+        if is_settlement_expired(&ctx.accounts.launch_auction, now) {
+            return Err(error!(ErrorCode::AuctionSettlementExpired));
+        }
+        
+        // The first call clears every commitment in a single curve purchase, so all
+        // participants pay the same price per token. A launch cancelled before that
+        // returns commitments through withdraw_commitment instead; one cancelled after
+        // still distributes what was cleared, so holders can claim refunds.
+        if !ctx.accounts.launch_auction.settled {
+            if ctx.accounts.project.is_cancelled {
+                return Err(error!(ErrorCode::ProjectCancelled));
+            }
+            
+            let project = &mut ctx.accounts.project;
+            let launch_auction = &mut ctx.accounts.launch_auction;
+            let clearing = clear_launch_auction(launch_auction, project, ctx.accounts.config.trading_fee_bps)?;
+            
+            let auction_info = launch_auction.to_account_info();
+            release_escrowed_lamports(&auction_info, &project.to_account_info(), clearing.curve_cost)?;
+            release_escrowed_lamports(&auction_info, &ctx.accounts.creator.to_account_info(), clearing.creator_fee)?;
+            release_escrowed_lamports(&auction_info, &ctx.accounts.fee_vault.to_account_info(), clearing.platform_fee)?;
+            
+            project.supply = project.supply.checked_add(clearing.tokens).ok_or(error!(ErrorCode::MathOverflow))?;
+            project.reserve_balance = project.reserve_balance.checked_add(clearing.curve_cost).ok_or(error!(ErrorCode::MathOverflow))?;
+            project.current_price = calculate_current_price(&project.curve_params, project.supply)?;
+            project.creator_fee_earned = project.creator_fee_earned
+                .checked_add(clearing.creator_fee)
+                .ok_or(error!(ErrorCode::MathOverflow))?;
+            project.platform_fee_earned = project.platform_fee_earned
+                .checked_add(clearing.platform_fee)
+                .ok_or(error!(ErrorCode::MathOverflow))?;
+            record_raise_progress(project);
+            
+            // Continuous trading opens now; the sniping window starts with its first buy
+            project.launch_at = now;
+            project.public_open_at = now;
+            
+            emit!(LaunchAuctionSettled {
+                mint: project.mint,
+                total_committed: launch_auction.total_committed,
+                commitment_count: launch_auction.commitment_count,
+                tokens: clearing.tokens,
+                curve_cost: clearing.curve_cost,
+                refund_pool: launch_auction.refund_pool,
+                timestamp: now,
+            });
+        }
+        
+        // Remaining accounts are (commitment, wallet, wallet token account) triples.
+        // Large auctions can be distributed over several transactions.
+        if ctx.remaining_accounts.len() % 3 != 0 {
+            return Err(error!(ErrorCode::InvalidAuctionAccounts));
+        }
+        
+        let mint_key = ctx.accounts.mint.key();
+        let project_seeds: &[&[u8]] = &[b"project", mint_key.as_ref(), &[ctx.bumps.project]];
+        let auction_info = ctx.accounts.launch_auction.to_account_info();
+        
+        for accounts in ctx.remaining_accounts.chunks(3) {
+            let (commitment_info, wallet_info, token_account_info) = (&accounts[0], &accounts[1], &accounts[2]);
+            
+            let commitment = Account::<AuctionCommitment>::try_from(commitment_info)?;
+            if commitment.mint != mint_key || commitment.wallet != wallet_info.key() {
+                return Err(error!(ErrorCode::InvalidAuctionAccounts));
+            }
+            
+            let token_account = InterfaceAccount::<token_interface::TokenAccount>::try_from(token_account_info)?;
+            if token_account.mint != mint_key || token_account.owner != commitment.wallet {
+                return Err(error!(ErrorCode::InvalidAuctionAccounts));
+            }
+            
+            let (tokens, refund) = allocate_commitment(&mut ctx.accounts.launch_auction, commitment.amount)?;
+            
+            if tokens > 0 {
+                token_2022::mint_to(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        MintTo {
+                            mint: ctx.accounts.mint.to_account_info(),
+                            to: token_account_info.clone(),
+                            authority: ctx.accounts.project.to_account_info(),
+                        },
+                        &[project_seeds],
+                    ),
+                    tokens,
+                )?;
+            }
+            
+            // Unused SOL and the commitment's rent go back to the wallet
+            release_escrowed_lamports(&auction_info, wallet_info, refund)?;
+            close_tracking_account(commitment_info, wallet_info)?;
+            
+            emit!(AuctionAllocationDistributed {
+                mint: mint_key,
+                wallet: commitment.wallet,
+                committed: commitment.amount,
+                tokens,
+                refund,
+                timestamp: now,
+            });
+        }
         
         Ok(())
     }
 
-    pub fn withdraw_platform_fees(
-        ctx: Context<WithdrawPlatformFees>,
-        amount: u64,
-    ) -> Result<()> {
-        // Only the launchpad authority can withdraw fees
-        if ctx.accounts.authority.key() != ctx.accounts.config.authority {
-            return Err(error!(ErrorCode::UnauthorizedAccess));
-        }
-        
-        // Check if the fee vault has enough balance
-        if ctx.accounts.fee_vault.lamports() < amount {
-            return Err(error!(ErrorCode::InsufficientFunds));
-        }
-        
-        // Transfer SOL from fee vault to recipient
-        **ctx.accounts.fee_vault.try_borrow_mut_lamports()? = ctx
-            .accounts.fee_vault
-            .lamports()
-            .checked_sub(amount)
-            .ok_or(error!(ErrorCode::InsufficientFunds))?;
-            
-        **ctx.accounts.recipient.try_borrow_mut_lamports()? = ctx
-            .accounts.recipient
-            .lamports()
-            .checked_add(amount)
-            .ok_or(error!(ErrorCode::MathOverflow))?;
-        
-        Ok(())
-    }
+    pub fn withdraw_commitment(
+        ctx: Context<WithdrawCommitment>,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let launch_auction = &mut ctx.accounts.launch_auction;
+        
+        // Escape hatch for an auction nobody settled in time, or whose launch was
+        // cancelled before it settled
+        if !is_withdrawable(launch_auction, &ctx.accounts.project, now) {
+            return Err(error!(ErrorCode::CommitmentLocked));
+        }
+        
+        let amount = ctx.accounts.commitment.amount;
+        remove_commitment(launch_auction, amount)?;
+        release_escrowed_lamports(&launch_auction.to_account_info(), &ctx.accounts.wallet.to_account_info(), amount)?;
+        
+        emit!(CommitmentWithdrawn {
+            mint: launch_auction.mint,
+            wallet: ctx.accounts.wallet.key(),
+            amount,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    pub fn cancel_launch(
+        ctx: Context<CancelLaunch>,
+    ) -> Result<()> {
+        let project = &mut ctx.accounts.project;
+        let now = Clock::get()?.unix_timestamp;
+        
+        // Only refundable launches can be cancelled
+        if project.min_raise.is_none() || project.is_graduated {
+            return Err(error!(ErrorCode::CancelNotAllowed));
+        }
+        if project.is_cancelled {
+            return Err(error!(ErrorCode::ProjectCancelled));
+        }
+        
+        // The creator can back out until someone else has bought in; anyone can
+        // cancel a launch that missed its minimum raise
+        let by_creator = ctx.accounts.authority.key() == project.creator && !project.has_third_party_buys;
+        if !by_creator && !has_failed(project, now) {
+            return Err(error!(ErrorCode::CancelNotAllowed));
+        }
+        
+        cancel_project(project, &ctx.accounts.authority.key(), by_creator, now);
+        
+        Ok(())
+    }
+
+    pub fn claim_refund(
+        ctx: Context<ClaimRefund>,
+        amount: u64,
+    ) -> Result<()> {
+        let project = &mut ctx.accounts.project;
+        let now = Clock::get()?.unix_timestamp;
+        
+        // A launch that missed its minimum raise is cancelled by its first refund
+        if !project.is_cancelled {
+            if !has_failed(project, now) {
+                return Err(error!(ErrorCode::ProjectNotCancelled));
+            }
+            cancel_project(project, &ctx.accounts.holder.key(), false, now);
+        }
+        
+        // Refunds are fee-free: every token burned gets an equal share of the reserve
+        let refund = calculate_refund(project, amount)?;
+        
+        token_2022::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    from: ctx.accounts.holder_token_account.to_account_info(),
+                    authority: ctx.accounts.holder.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        
+        // Transfer SOL from project reserve to holder
+        **project.to_account_info().try_borrow_mut_lamports()? = project
+            .to_account_info()
+            .lamports()
+            .checked_sub(refund)
+            .ok_or(error!(ErrorCode::InsufficientFunds))?;
+            
+        **ctx.accounts.holder.try_borrow_mut_lamports()? = ctx
+            .accounts.holder
+            .lamports()
+            .checked_add(refund)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+        
+        project.supply = project.supply.checked_sub(amount).ok_or(error!(ErrorCode::MathOverflow))?;
+        project.reserve_balance = project.reserve_balance.checked_sub(refund).ok_or(error!(ErrorCode::MathOverflow))?;
+        project.current_price = calculate_current_price(&project.curve_params, project.supply)?;
+        
+        // The disclosed allocation can never exceed what the creator still holds
+        let holder_balance = ctx.accounts.holder_token_account.amount.checked_sub(amount)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+        cap_creator_allocation(project, &ctx.accounts.holder.key(), holder_balance);
+        
+        emit!(RefundClaimed {
+            mint: project.mint,
+            holder: ctx.accounts.holder.key(),
+            amount,
+            refund,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    pub fn buy_tokens<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BuyTokens<'info>>,
+        amount: u64,
+        whitelist_proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let project = &mut ctx.accounts.project;
+        let config = &ctx.accounts.config;
+        
+        // Scheduled launches take no buys before launch_at, then only whitelisted
+        // wallets until public trading opens
+        let now = Clock::get()?.unix_timestamp;
+        let phase = launch_phase(project, now);
+        match phase {
+            LaunchPhase::Pending => return Err(error!(ErrorCode::LaunchNotStarted)),
+            LaunchPhase::Whitelist => {
+                let whitelist_root = project.whitelist_root.ok_or(error!(ErrorCode::NotWhitelisted))?;
+                if !verify_whitelist_proof(&whitelist_root, &ctx.accounts.buyer.key(), &whitelist_proof) {
+                    return Err(error!(ErrorCode::NotWhitelisted));
+                }
+                
+                let whitelist_purchase = ctx.accounts.whitelist_purchase
+                    .as_mut()
+                    .ok_or(error!(ErrorCode::WhitelistPurchaseRequired))?;
+                record_whitelist_purchase(
+                    whitelist_purchase,
+                    &project.mint,
+                    &ctx.accounts.buyer.key(),
+                    amount,
+                    project.whitelist_cap,
+                )?;
+            }
+            LaunchPhase::Public => {}
+        }
+        
+        // Cancelled and failed launches only pay out refunds
+        if is_cancelled(project, now) {
+            return Err(error!(ErrorCode::ProjectCancelled));
+        }
+        
+        let exemption = ctx.accounts.exempt_address.as_deref();
+        let limits_exempt = has_exemption(exemption, &ctx.accounts.buyer.key(), EXEMPT_LIMITS);
+        
+        // Enforce the per-transaction limit during the curve phase
+        if !limits_exempt {
+            check_max_buy(project, amount)?;
+        }
+        
+        // Enforce the cooldown between consecutive buys from the same wallet or cluster
+        if project.buy_cooldown_secs.is_some() && !limits_exempt {
+            let bundle_tracker = &ctx.accounts.bundle_tracker;
+            check_buy_cooldown(project, bundle_tracker.last_buy_at, bundle_tracker.related_wallets.len(), now)?;
+            
+            if let Some(bundle_cluster) = &ctx.accounts.bundle_cluster {
+                let other_members = bundle_cluster.member_count.saturating_sub(1) as usize;
+                check_buy_cooldown(project, bundle_cluster.last_buy_at, other_members, now)?;
+            }
+        }
+        
+        // Calculate the price based on the bond curve
+        let price = calculate_buy_price(
+            &project.curve_params,
+            project.supply,
+            amount,
+        )?;
+        
+        // Buys in the first slots after launch pay the snipe tax
+        let slot = Clock::get()?.slot;
+        if project.launch_slot == SCHEDULED_LAUNCH_SLOT && phase == LaunchPhase::Public {
+            project.launch_slot = slot;
+            ctx.accounts.launch_ledger.launch_slot = slot;
+        }
+        let is_snipe = is_in_snipe_window(project.launch_slot, slot, config.snipe_window_slots);
+        let snipe_tax = if is_snipe {
+            calculate_snipe_tax(price, config.snipe_tax_bps)?
+        } else {
+            0
+        };
+        
+        // Referral payouts and fee tiers are kept in SOL, so quote-token trades cannot carry them
+        if project.quote_mint.is_some() && ctx.accounts.referrer.is_some() {
+            return Err(error!(ErrorCode::UnsupportedReferralQuote));
+        }
+        if project.quote_mint.is_some() && ctx.accounts.trader_stats.is_some() {
+            return Err(error!(ErrorCode::UnsupportedFeeTierQuote));
+        }
+        
+        // High-volume traders pay the fee of the tier their rolling volume reaches
+        let base_fee_bps = match &ctx.accounts.trader_stats {
+            Some(trader_stats) => effective_fee_bps(config, rolling_volume(trader_stats, now)),
+            None => config.trading_fee_bps,
+        };
+        
+        // Buys right after launch pay the project's decaying launch fee instead
+        let fee_bps = calculate_launch_fee_bps(project.launch_fee.as_ref(), project.launch_slot, slot, base_fee_bps);
+        
+        // Calculate trading fee
+        let total_fee = price
+            .checked_mul(fee_bps as u64)
+            .ok_or(error!(ErrorCode::MathOverflow))?
+            .checked_div(10000)
+            .ok_or(error!(ErrorCode::DivisionByZero))?;
+        let base_fee = price
+            .checked_mul(base_fee_bps as u64)
+            .ok_or(error!(ErrorCode::MathOverflow))?
+            .checked_div(10000)
+            .ok_or(error!(ErrorCode::DivisionByZero))?;
+        
+        // Split fee 50/50 between creator and platform, launch fee excess included
+        let creator_fee = total_fee.checked_div(2).ok_or(error!(ErrorCode::DivisionByZero))?;
+        let platform_fee = total_fee.checked_sub(creator_fee).ok_or(error!(ErrorCode::MathOverflow))?;
+        
+        // Fees and the snipe tax are charged on top of the curve price, so the reserve
+        // always receives the full curve cost and sells stay covered
+        let reserve_amount = price;
+        let total_cost = price
+            .checked_add(total_fee)
+            .ok_or(error!(ErrorCode::MathOverflow))?
+            .checked_add(snipe_tax)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+        
+        // Referred trades pay the referrer out of the platform share. Referrers earn on
+        // the base fee only, not on the launch fee excess.
+        let referral_fee = match &ctx.accounts.referrer {
+            Some(referrer) => {
+                if referrer.wallet == ctx.accounts.buyer.key() {
+                    return Err(error!(ErrorCode::SelfReferral));
+                }
+                calculate_referral_fee(base_fee, platform_fee, config.referral_fee_bps)?
+            }
+            None => 0,
+        };
+        let platform_fee = platform_fee.checked_sub(referral_fee).ok_or(error!(ErrorCode::MathOverflow))?;
+        
+        if project.quote_mint.is_some() {
+            // Projects priced in an SPL token settle the quote leg with transfer_checked
+            let quote = require_quote_accounts(
+                project,
+                &project.key(),
+                &ctx.accounts.fee_vault.key(),
+                &ctx.accounts.buyer.key(),
+                &ctx.accounts.quote_mint,
+                &ctx.accounts.quote_vault,
+                &ctx.accounts.buyer_quote_account,
+                &ctx.accounts.creator_quote_account,
+                &ctx.accounts.fee_vault_quote_account,
+                &ctx.accounts.quote_token_program,
+            )?;
+            
+            if quote.trader_account.amount < total_cost {
+                return Err(error!(ErrorCode::InsufficientFunds));
+            }
+            
+            let buyer_info = ctx.accounts.buyer.to_account_info();
+            let platform_amount = platform_fee.checked_add(snipe_tax).ok_or(error!(ErrorCode::MathOverflow))?;
+            
+            transfer_quote(&quote, quote.trader_account, quote.vault, buyer_info.clone(), reserve_amount, &[])?;
+            transfer_quote(&quote, quote.trader_account, quote.creator_account, buyer_info.clone(), creator_fee, &[])?;
+            transfer_quote(&quote, quote.trader_account, quote.fee_account, buyer_info, platform_amount, &[])?;
+        } else {
+            // Check if buyer has enough SOL
+            if ctx.accounts.buyer.lamports() < total_cost {
+                return Err(error!(ErrorCode::InsufficientFunds));
+            }
+            
+            // Transfer SOL from buyer to project reserve
+            invoke(
+                &system_instruction::transfer(
+                    &ctx.accounts.buyer.key(),
+                    &project.key(),
+                    reserve_amount,
+                ),
+                &[
+                    ctx.accounts.buyer.to_account_info(),
+                    ctx.accounts.project.to_account_info(),
+                    ctx.accounts.system_program.to_account_info(),
+                ],
+            )?;
+            
+            // Transfer creator fee to creator
+            if creator_fee > 0 {
+                invoke(
+                    &system_instruction::transfer(
+                        &ctx.accounts.buyer.key(),
+                        &project.creator,
+                        creator_fee,
+                    ),
+                    &[
+                        ctx.accounts.buyer.to_account_info(),
+                        ctx.accounts.system_program.to_account_info(),
+                    ],
+                )?;
+            }
+            
+            // Transfer platform fee to fee vault
+            if platform_fee > 0 {
+                invoke(
+                    &system_instruction::transfer(
+                        &ctx.accounts.buyer.key(),
+                        &ctx.accounts.fee_vault.key(),
+                        platform_fee,
+                    ),
+                    &[
+                        ctx.accounts.buyer.to_account_info(),
+                        ctx.accounts.fee_vault.to_account_info(),
+                        ctx.accounts.system_program.to_account_info(),
+                    ],
+                )?;
+            }
+            
+            // Transfer referral fee to the referrer account, where it waits to be claimed
+            if let Some(referrer) = ctx.accounts.referrer.as_mut().filter(|_| referral_fee > 0) {
+                invoke(
+                    &system_instruction::transfer(
+                        &ctx.accounts.buyer.key(),
+                        &referrer.key(),
+                        referral_fee,
+                    ),
+                    &[
+                        ctx.accounts.buyer.to_account_info(),
+                        referrer.to_account_info(),
+                        ctx.accounts.system_program.to_account_info(),
+                    ],
+                )?;
+                
+                record_referral(referrer, price, referral_fee)?;
+            }
+            
+            // Transfer snipe tax to fee vault
+            if snipe_tax > 0 {
+                invoke(
+                    &system_instruction::transfer(
+                        &ctx.accounts.buyer.key(),
+                        &ctx.accounts.fee_vault.key(),
+                        snipe_tax,
+                    ),
+                    &[
+                        ctx.accounts.buyer.to_account_info(),
+                        ctx.accounts.fee_vault.to_account_info(),
+                        ctx.accounts.system_program.to_account_info(),
+                    ],
+                )?;
+            }
+        }
+        
+        // Update fees earned
+        project.creator_fee_earned = project.creator_fee_earned
+            .checked_add(creator_fee)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+        project.platform_fee_earned = project.platform_fee_earned
+            .checked_add(platform_fee)
+            .ok_or(error!(ErrorCode::MathOverflow))?
+            .checked_add(snipe_tax)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+        
+        // Record the buy in the launch ledger
+        record_launch_buy(&mut ctx.accounts.launch_ledger, slot, amount)?;
+        
+        if let Some(trader_stats) = ctx.accounts.trader_stats.as_mut() {
+            record_volume(trader_stats, &ctx.accounts.buyer.key(), price, now)?;
+        }
+        
+        // Mint tokens to buyer, signed by the project PDA as mint authority
+        let mint_key = project.mint;
+        let project_seeds: &[&[u8]] = &[b"project", mint_key.as_ref(), &[ctx.bumps.project]];
+        token_2022::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.buyer_token_account.to_account_info(),
+                    authority: project.to_account_info(),
+                },
+                &[project_seeds],
+            ),
+            amount,
+        )?;
+        
+        project.supply = project.supply.checked_add(amount).ok_or(error!(ErrorCode::MathOverflow))?;
+        project.reserve_balance = project.reserve_balance.checked_add(reserve_amount).ok_or(error!(ErrorCode::MathOverflow))?;
+        project.current_price = calculate_current_price(&project.curve_params, project.supply)?;
+        record_raise_progress(project);
+        
+        // Buys from anyone but the creator rule out an early cancellation
+        if ctx.accounts.buyer.key() != project.creator {
+            project.has_third_party_buys = true;
+        }
+        
+        // Enforce the max-wallet limit against the buyer's post-trade balance
+        let buyer_balance = ctx.accounts.buyer_token_account.amount.checked_add(amount)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+        if !limits_exempt {
+            check_max_wallet(project, buyer_balance)?;
+        }
+        
+        // Check if the token is eligible for graduation
+        if !project.is_graduated && is_eligible_for_graduation(project.supply, project.premint_supply, project.current_price, project.graduation_market_cap)? {
+            // Mark as eligible for graduation
+            // In a real implementation, you might want to emit an event or set a flag
+            msg!("Token is now eligible for graduation!");
+        }
+        
+        // Update the buyer's bundle tracker
+        record_rent_payer(&mut ctx.accounts.bundle_tracker.rent_payer, &ctx.accounts.buyer.key());
+        let new_balance = ctx.accounts.buyer_token_account.amount.checked_add(amount)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+        let new_balance = bundle_balance(project, &ctx.accounts.buyer.key(), new_balance);
+        
+        // Combine the balances of related wallets passed as remaining accounts
+        update_bundle_cluster(
+            &mut ctx.accounts.bundle_tracker,
+            &ctx.accounts.buyer.key(),
+            &project.mint,
+            new_balance,
+            ctx.remaining_accounts,
+            config.relationship_threshold,
+            config.bundle_threshold_percentage,
+            project.supply,
+            config.bundle_flag_duration,
+            ctx.accounts.exempt_address.as_deref()
+        )?;
+        
+        // Record the signals relationship inference compares between wallets
+        let fee_payer = ctx.accounts.fee_payer
+            .as_ref()
+            .map(|fee_payer| fee_payer.key())
+            .unwrap_or(ctx.accounts.buyer.key());
+        
+        record_trade_signals(
+            &mut ctx.accounts.bundle_tracker,
+            &fee_payer,
+            &ctx.accounts.instructions_sysvar,
+            slot
+        )?;
+        
+        let bundle_membership = load_membership(&ctx.accounts.bundle_membership)?;
+        let in_cluster = bundle_membership
+            .as_ref()
+            .is_some_and(|membership| membership.cluster != Pubkey::default());
+        
+        // Auto-tag snipers without a cluster into the project's sniper cluster. Only they
+        // need a membership created; everyone else's is loaded if it exists.
+        if is_snipe && !in_cluster {
+            let bundle_cluster = ctx.accounts.bundle_cluster
+                .as_mut()
+                .ok_or(error!(ErrorCode::SniperClusterRequired))?;
+            
+            if bundle_cluster.mint != project.mint || bundle_cluster.cluster_id != SNIPER_CLUSTER_ID {
+                return Err(error!(ErrorCode::SniperClusterRequired));
+            }
+            
+            let mut bundle_membership = match bundle_membership {
+                Some(bundle_membership) => bundle_membership,
+                None => create_membership(
+                    &ctx.accounts.bundle_membership,
+                    &ctx.accounts.buyer.to_account_info(),
+                    &project.mint,
+                    &ctx.accounts.buyer.key(),
+                    ctx.bumps.bundle_membership,
+                    &ctx.accounts.system_program.to_account_info(),
+                )?,
+            };
+            
+            join_bundle_cluster(
+                bundle_cluster,
+                &mut bundle_membership,
+                &ctx.accounts.buyer.key(),
+                &project.mint
+            )?;
+            update_cluster_member_balance(
+                bundle_cluster,
+                &mut bundle_membership,
+                new_balance,
+                config.bundle_threshold_percentage,
+                project.supply
+            )?;
+            bundle_membership.exit(&crate::ID)?;
+            bundle_cluster.last_buy_at = now;
+        } else {
+            // Update the aggregate balance of the wallet's bundle cluster, if it belongs to one
+            let is_member = update_member_cluster(
+                &ctx.accounts.bundle_membership,
+                ctx.accounts.bundle_cluster.as_mut(),
+                new_balance,
+                config.bundle_threshold_percentage,
+                project.supply
+            )?;
+            
+            // Members start their cluster's cooldown as well as their own
+            if is_member {
+                if let Some(bundle_cluster) = ctx.accounts.bundle_cluster.as_mut() {
+                    bundle_cluster.last_buy_at = now;
+                }
+            }
+        }
+        
+        ctx.accounts.bundle_tracker.last_buy_at = now;
+        
+        Ok(())
+    }
+
+    pub fn sell_tokens<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SellTokens<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        let project = &mut ctx.accounts.project;
+        let config = &ctx.accounts.config;
+        
+        // Nothing trades against the curve before a scheduled or auctioned launch opens
+        let now = Clock::get()?.unix_timestamp;
+        if launch_phase(project, now) == LaunchPhase::Pending {
+            return Err(error!(ErrorCode::LaunchNotStarted));
+        }
+        
+        // Holders of a cancelled or failed launch claim refunds instead
+        if is_cancelled(project, now) {
+            return Err(error!(ErrorCode::ProjectCancelled));
+        }
+        
+        // Check if the project has enough supply
+        if project.supply < amount {
+            return Err(error!(ErrorCode::InsufficientSupply));
+        }
+        
+        // Calculate the sell price based on the bond curve
+        let price = calculate_sell_price(
+            &project.curve_params,
+            project.supply,
+            amount,
+        )?;
+        
+        // Check if the project has enough reserve balance
+        if project.reserve_balance < price {
+            return Err(error!(ErrorCode::InsufficientFunds));
+        }
+        
+        // Referral payouts and fee tiers are kept in SOL, so quote-token trades cannot carry them
+        if project.quote_mint.is_some() && ctx.accounts.referrer.is_some() {
+            return Err(error!(ErrorCode::UnsupportedReferralQuote));
+        }
+        if project.quote_mint.is_some() && ctx.accounts.trader_stats.is_some() {
+            return Err(error!(ErrorCode::UnsupportedFeeTierQuote));
+        }
+        
+        // High-volume traders pay the fee of the tier their rolling volume reaches
+        let fee_bps = match &ctx.accounts.trader_stats {
+            Some(trader_stats) => effective_fee_bps(config, rolling_volume(trader_stats, now)),
+            None => config.trading_fee_bps,
+        };
+        
+        // Calculate trading fee
+        let total_fee = price
+            .checked_mul(fee_bps as u64)
+            .ok_or(error!(ErrorCode::MathOverflow))?
+            .checked_div(10000)
+            .ok_or(error!(ErrorCode::DivisionByZero))?;
+        
+        // Split fee 50/50 between creator and platform
+        let creator_fee = total_fee.checked_div(2).ok_or(error!(ErrorCode::DivisionByZero))?;
+        let platform_fee = total_fee.checked_sub(creator_fee).ok_or(error!(ErrorCode::MathOverflow))?;
+        
+        let payout_amount = price.checked_sub(total_fee).ok_or(error!(ErrorCode::MathOverflow))?;
+        
+        // Referred trades pay the referrer out of the platform share
+        let referral_fee = match &ctx.accounts.referrer {
+            Some(referrer) => {
+                if referrer.wallet == ctx.accounts.seller.key() {
+                    return Err(error!(ErrorCode::SelfReferral));
+                }
+                calculate_referral_fee(total_fee, platform_fee, config.referral_fee_bps)?
+            }
+            None => 0,
+        };
+        let platform_fee = platform_fee.checked_sub(referral_fee).ok_or(error!(ErrorCode::MathOverflow))?;
+        
+        if project.quote_mint.is_some() {
+            // The quote vault is owned by the project PDA, which signs the payout
+            let quote = require_quote_accounts(
+                project,
+                &project.key(),
+                &ctx.accounts.fee_vault.key(),
+                &ctx.accounts.seller.key(),
+                &ctx.accounts.quote_mint,
+                &ctx.accounts.quote_vault,
+                &ctx.accounts.seller_quote_account,
+                &ctx.accounts.creator_quote_account,
+                &ctx.accounts.fee_vault_quote_account,
+                &ctx.accounts.quote_token_program,
+            )?;
+            
+            let mint_key = project.mint;
+            let project_seeds: &[&[u8]] = &[b"project", mint_key.as_ref(), &[ctx.bumps.project]];
+            let project_info = project.to_account_info();
+            
+            transfer_quote(&quote, quote.vault, quote.trader_account, project_info.clone(), payout_amount, &[project_seeds])?;
+            transfer_quote(&quote, quote.vault, quote.creator_account, project_info.clone(), creator_fee, &[project_seeds])?;
+            transfer_quote(&quote, quote.vault, quote.fee_account, project_info, platform_fee, &[project_seeds])?;
+        } else {
+            // Transfer SOL from project reserve to seller
+            **project.to_account_info().try_borrow_mut_lamports()? = project
+                .to_account_info()
+                .lamports()
+                .checked_sub(payout_amount)
+                .ok_or(error!(ErrorCode::InsufficientFunds))?;
+                
+            **ctx.accounts.seller.try_borrow_mut_lamports()? = ctx
+                .accounts.seller
+                .lamports()
+                .checked_add(payout_amount)
+                .ok_or(error!(ErrorCode::MathOverflow))?;
+            
+            // Transfer creator fee
+            if creator_fee > 0 {
+                **project.to_account_info().try_borrow_mut_lamports()? = project
+                    .to_account_info()
+                    .lamports()
+                    .checked_sub(creator_fee)
+                    .ok_or(error!(ErrorCode::InsufficientFunds))?;
+                    
+                // In a real implementation, you would transfer to the creator
+                // For simplicity, we'll just update the project state
+            }
+            
+            // Transfer platform fee
+            if platform_fee > 0 {
+                **project.to_account_info().try_borrow_mut_lamports()? = project
+                    .to_account_info()
+                    .lamports()
+                    .checked_sub(platform_fee)
+                    .ok_or(error!(ErrorCode::InsufficientFunds))?;
+                    
+                **ctx.accounts.fee_vault.try_borrow_mut_lamports()? = ctx
+                    .accounts.fee_vault
+                    .lamports()
+                    .checked_add(platform_fee)
+                    .ok_or(error!(ErrorCode::MathOverflow))?;
+            }
+            
+            // Transfer referral fee to the referrer account, where it waits to be claimed
+            if let Some(referrer) = ctx.accounts.referrer.as_mut().filter(|_| referral_fee > 0) {
+                **project.to_account_info().try_borrow_mut_lamports()? = project
+                    .to_account_info()
+                    .lamports()
+                    .checked_sub(referral_fee)
+                    .ok_or(error!(ErrorCode::InsufficientFunds))?;
+                    
+                **referrer.to_account_info().try_borrow_mut_lamports()? = referrer
+                    .to_account_info()
+                    .lamports()
+                    .checked_add(referral_fee)
+                    .ok_or(error!(ErrorCode::MathOverflow))?;
+                
+                record_referral(referrer, price, referral_fee)?;
+            }
+        }
+        
+        // Update fees earned
+        project.creator_fee_earned = project.creator_fee_earned
+            .checked_add(creator_fee)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+        project.platform_fee_earned = project.platform_fee_earned
+            .checked_add(platform_fee)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+        
+        if let Some(trader_stats) = ctx.accounts.trader_stats.as_mut() {
+            record_volume(trader_stats, &ctx.accounts.seller.key(), price, now)?;
+        }
+        
+        // Burn tokens from seller
+        token_2022::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    from: ctx.accounts.seller_token_account.to_account_info(),
+                    authority: ctx.accounts.seller.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        
+        project.supply = project.supply.checked_sub(amount).ok_or(error!(ErrorCode::MathOverflow))?;
+        project.reserve_balance = project.reserve_balance.checked_sub(price).ok_or(error!(ErrorCode::MathOverflow))?;
+        project.current_price = calculate_current_price(&project.curve_params, project.supply)?;
+        
+        // The disclosed allocation can never exceed what the creator still holds
+        let seller_balance = ctx.accounts.seller_token_account.amount.checked_sub(amount)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+        cap_creator_allocation(project, &ctx.accounts.seller.key(), seller_balance);
+        
+        // Update the seller's bundle tracker
+        record_rent_payer(&mut ctx.accounts.bundle_tracker.rent_payer, &ctx.accounts.seller.key());
+        let new_balance = ctx.accounts.seller_token_account.amount.checked_sub(amount)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+        let new_balance = bundle_balance(project, &ctx.accounts.seller.key(), new_balance);
+        
+        // Combine the balances of related wallets passed as remaining accounts
+        update_bundle_cluster(
+            &mut ctx.accounts.bundle_tracker,
+            &ctx.accounts.seller.key(),
+            &project.mint,
+            new_balance,
+            ctx.remaining_accounts,
+            config.relationship_threshold,
+            config.bundle_threshold_percentage,
+            project.supply,
+            config.bundle_flag_duration,
+            ctx.accounts.exempt_address.as_deref()
+        )?;
+        
+        // Update the aggregate balance of the wallet's bundle cluster, if it belongs to one
+        update_member_cluster(
+            &ctx.accounts.bundle_membership,
+            ctx.accounts.bundle_cluster.as_mut(),
+            new_balance,
+            config.bundle_threshold_percentage,
+            project.supply
+        )?;
+        
+        Ok(())
+    }
+
+    pub fn place_order<'info>(
+        ctx: Context<'_, '_, 'info, 'info, PlaceOrder<'info>>,
+        order_id: u64,
+        side: OrderSide,
+        trigger_price: u64,
+        amount: u64,
+        max_sol_cost: Option<u64>,
+        min_proceeds: Option<u64>,
+        keeper_fee: u64,
+        expires_at: i64,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        validate_order(side, trigger_price, amount, max_sol_cost, min_proceeds, expires_at, now)?;
+        
+        // Orders settle against the SOL reserve
+        if ctx.accounts.project.quote_mint.is_some() {
+            return Err(error!(ErrorCode::UnsupportedOrderQuote));
+        }
+        
+        let (escrowed, escrowed_lamports) = order_escrow(side, amount, max_sol_cost, keeper_fee)?;
+        
+        if escrowed_lamports > 0 {
+            invoke(
+                &system_instruction::transfer(
+                    &ctx.accounts.owner.key(),
+                    &ctx.accounts.order.key(),
+                    escrowed_lamports,
+                ),
+                &[
+                    ctx.accounts.owner.to_account_info(),
+                    ctx.accounts.order.to_account_info(),
+                    ctx.accounts.system_program.to_account_info(),
+                ],
+            )?;
+        }
+        
+        if side == OrderSide::Sell {
+            let owner_token_account = ctx.accounts.owner_token_account
+                .as_ref()
+                .ok_or(error!(ErrorCode::OrderAccountsRequired))?;
+            
+            // Escrowed tokens are burned and recorded on the order, since a transfer
+            // would re-enter this program through the mint's transfer hook
+            token_2022::burn(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Burn {
+                        mint: ctx.accounts.mint.to_account_info(),
+                        from: owner_token_account.to_account_info(),
+                        authority: ctx.accounts.owner.to_account_info(),
+                    },
+                ),
+                amount,
+            )?;
+            
+            // The disclosed allocation can never exceed what the creator still holds
+            let owner_balance = owner_token_account.amount.checked_sub(amount)
+                .ok_or(error!(ErrorCode::MathOverflow))?;
+            cap_creator_allocation(&mut ctx.accounts.project, &ctx.accounts.owner.key(), owner_balance);
+        }
+        
+        let order_key = ctx.accounts.order.key();
+        let order = &mut ctx.accounts.order;
+        order.mint = ctx.accounts.mint.key();
+        order.owner = ctx.accounts.owner.key();
+        order.order_id = order_id;
+        order.side = side;
+        order.trigger_price = trigger_price;
+        order.amount = amount;
+        order.escrowed = escrowed;
+        order.min_proceeds = min_proceeds.unwrap_or(0);
+        order.keeper_fee = keeper_fee;
+        order.expires_at = expires_at;
+        order.created_at = now;
+        
+        emit!(OrderPlaced {
+            mint: order.mint,
+            owner: order.owner,
+            order: order_key,
+            side,
+            trigger_price,
+            amount,
+            escrowed,
+            min_proceeds: order.min_proceeds,
+            keeper_fee,
+            expires_at,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    pub fn cancel_order<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CancelOrder<'info>>,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        
+        // Owners can cancel at any time; anyone can clean up an expired order, and
+        // the escrow always goes back to the owner
+        if ctx.accounts.authority.key() != ctx.accounts.order.owner && now < ctx.accounts.order.expires_at {
+            return Err(error!(ErrorCode::UnauthorizedAccess));
+        }
+        
+        // Sell orders burned their tokens when placed; mint them back to the owner
+        let tokens = take_escrowed_tokens(&mut ctx.accounts.order);
+        if tokens > 0 {
+            let owner_token_account = ctx.accounts.owner_token_account
+                .as_ref()
+                .ok_or(error!(ErrorCode::OrderAccountsRequired))?;
+            
+            let mint_key = ctx.accounts.mint.key();
+            let project_seeds: &[&[u8]] = &[b"project", mint_key.as_ref(), &[ctx.bumps.project]];
+            
+            token_2022::mint_to(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    MintTo {
+                        mint: ctx.accounts.mint.to_account_info(),
+                        to: owner_token_account.to_account_info(),
+                        authority: ctx.accounts.project.to_account_info(),
+                    },
+                    &[project_seeds],
+                ),
+                tokens,
+            )?;
+        }
+        
+        let order = &ctx.accounts.order;
+        emit!(OrderCancelled {
+            mint: order.mint,
+            owner: order.owner,
+            order: order.key(),
+            cancelled_by: ctx.accounts.authority.key(),
+            timestamp: now,
+        });
+        
+        // Closing the order returns its escrowed lamports and rent to the owner
+        Ok(())
+    }
+
+    pub fn execute_order<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteOrder<'info>>,
+    ) -> Result<()> {
+        let config = &ctx.accounts.config;
+        let now = Clock::get()?.unix_timestamp;
+        let slot = Clock::get()?.slot;
+        
+        check_executable(&ctx.accounts.order, &ctx.accounts.project, now, slot, config.snipe_window_slots, config.trading_fee_bps)?;
+        
+        // A tracker the keeper created refunds the keeper when it is closed
+        record_rent_payer(&mut ctx.accounts.bundle_tracker.rent_payer, &ctx.accounts.keeper.key());
+        
+        let order = &ctx.accounts.order;
+        let (side, amount, owner_key) = (order.side, order.amount, order.owner);
+        let mint_key = ctx.accounts.mint.key();
+        let project_seeds: &[&[u8]] = &[b"project", mint_key.as_ref(), &[ctx.bumps.project]];
+        let order_info = ctx.accounts.order.to_account_info();
+        let project = &mut ctx.accounts.project;
+        
+        let price = match side {
+            OrderSide::Buy => calculate_buy_price(&project.curve_params, project.supply, amount)?,
+            OrderSide::Sell => {
+                if project.supply < amount {
+                    return Err(error!(ErrorCode::InsufficientSupply));
+                }
+                calculate_sell_price(&project.curve_params, project.supply, amount)?
+            }
+        };
+        
+        // Orders pay the base trading fee, split between the creator and the platform.
+        // Buys pay it on top of the curve price, sells out of the proceeds.
+        let total_fee = price
+            .checked_mul(config.trading_fee_bps as u64)
+            .ok_or(error!(ErrorCode::MathOverflow))?
+            .checked_div(10000)
+            .ok_or(error!(ErrorCode::DivisionByZero))?;
+        let creator_fee = total_fee.checked_div(2).ok_or(error!(ErrorCode::DivisionByZero))?;
+        let platform_fee = total_fee.checked_sub(creator_fee).ok_or(error!(ErrorCode::MathOverflow))?;
+        let net_amount = price.checked_sub(total_fee).ok_or(error!(ErrorCode::MathOverflow))?;
+        
+        match side {
+            OrderSide::Buy => {
+                let owner_token_account = ctx.accounts.owner_token_account
+                    .as_ref()
+                    .ok_or(error!(ErrorCode::OrderAccountsRequired))?;
+                
+                let exemption = ctx.accounts.exempt_address.as_deref();
+                let limits_exempt = has_exemption(exemption, &owner_key, EXEMPT_LIMITS);
+                
+                if !limits_exempt {
+                    check_max_buy(project, amount)?;
+                }
+                
+                // Orders honour the owner's buy cooldown like a direct buy
+                if project.buy_cooldown_secs.is_some() && !limits_exempt {
+                    let bundle_tracker = &ctx.accounts.bundle_tracker;
+                    check_buy_cooldown(project, bundle_tracker.last_buy_at, bundle_tracker.related_wallets.len(), now)?;
+                    
+                    if let Some(bundle_cluster) = &ctx.accounts.bundle_cluster {
+                        let other_members = bundle_cluster.member_count.saturating_sub(1) as usize;
+                        check_buy_cooldown(project, bundle_cluster.last_buy_at, other_members, now)?;
+                    }
+                }
+                
+                // The price can have moved past the trigger since the order was placed
+                let total_cost = price.checked_add(total_fee).ok_or(error!(ErrorCode::MathOverflow))?;
+                if total_cost > ctx.accounts.order.escrowed {
+                    return Err(error!(ErrorCode::SlippageExceeded));
+                }
+                
+                release_escrowed_lamports(&order_info, &project.to_account_info(), price)?;
+                release_escrowed_lamports(&order_info, &ctx.accounts.creator.to_account_info(), creator_fee)?;
+                release_escrowed_lamports(&order_info, &ctx.accounts.fee_vault.to_account_info(), platform_fee)?;
+                
+                token_2022::mint_to(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        MintTo {
+                            mint: ctx.accounts.mint.to_account_info(),
+                            to: owner_token_account.to_account_info(),
+                            authority: project.to_account_info(),
+                        },
+                        &[project_seeds],
+                    ),
+                    amount,
+                )?;
+                
+                project.supply = project.supply.checked_add(amount).ok_or(error!(ErrorCode::MathOverflow))?;
+                project.reserve_balance = project.reserve_balance.checked_add(price).ok_or(error!(ErrorCode::MathOverflow))?;
+                project.current_price = calculate_current_price(&project.curve_params, project.supply)?;
+                record_raise_progress(project);
+                
+                if owner_key != project.creator {
+                    project.has_third_party_buys = true;
+                }
+                
+                let owner_balance = owner_token_account.amount.checked_add(amount)
+                    .ok_or(error!(ErrorCode::MathOverflow))?;
+                if !limits_exempt {
+                    check_max_wallet(project, owner_balance)?;
+                }
+                
+                // Track the filled buy for bundle detection like a direct buy
+                let new_balance = bundle_balance(project, &owner_key, owner_balance);
+                update_bundle_cluster(
+                    &mut ctx.accounts.bundle_tracker,
+                    &owner_key,
+                    &project.mint,
+                    new_balance,
+                    ctx.remaining_accounts,
+                    config.relationship_threshold,
+                    config.bundle_threshold_percentage,
+                    project.supply,
+                    config.bundle_flag_duration,
+                    exemption
+                )?;
+                
+                let is_member = update_member_cluster(
+                    &ctx.accounts.bundle_membership,
+                    ctx.accounts.bundle_cluster.as_mut(),
+                    new_balance,
+                    config.bundle_threshold_percentage,
+                    project.supply
+                )?;
+                
+                // A filled buy starts the owner's cooldown like a direct buy
+                ctx.accounts.bundle_tracker.last_buy_at = now;
+                if is_member {
+                    if let Some(bundle_cluster) = ctx.accounts.bundle_cluster.as_mut() {
+                        bundle_cluster.last_buy_at = now;
+                    }
+                }
+            }
+            OrderSide::Sell => {
+                if project.reserve_balance < price {
+                    return Err(error!(ErrorCode::InsufficientFunds));
+                }
+                
+                // The price can have fallen back since the order was triggered
+                if net_amount < ctx.accounts.order.min_proceeds {
+                    return Err(error!(ErrorCode::SlippageExceeded));
+                }
+                
+                // The tokens were burned when the order was placed
+                if take_escrowed_tokens(&mut ctx.accounts.order) != amount {
+                    return Err(error!(ErrorCode::InvalidOrder));
+                }
+                
+                let project_info = project.to_account_info();
+                release_escrowed_lamports(&project_info, &ctx.accounts.owner.to_account_info(), net_amount)?;
+                release_escrowed_lamports(&project_info, &ctx.accounts.creator.to_account_info(), creator_fee)?;
+                release_escrowed_lamports(&project_info, &ctx.accounts.fee_vault.to_account_info(), platform_fee)?;
+                
+                project.supply = project.supply.checked_sub(amount).ok_or(error!(ErrorCode::MathOverflow))?;
+                project.reserve_balance = project.reserve_balance.checked_sub(price).ok_or(error!(ErrorCode::MathOverflow))?;
+                project.current_price = calculate_current_price(&project.curve_params, project.supply)?;
+            }
+        }
+        
+        project.creator_fee_earned = project.creator_fee_earned
+            .checked_add(creator_fee)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+        project.platform_fee_earned = project.platform_fee_earned
+            .checked_add(platform_fee)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+        
+        // Pay the keeper; closing the order returns the rest of the escrow to the owner
+        let keeper_fee = ctx.accounts.order.keeper_fee;
+        release_escrowed_lamports(&order_info, &ctx.accounts.keeper.to_account_info(), keeper_fee)?;
+        
+        emit!(OrderExecuted {
+            mint: mint_key,
+            owner: owner_key,
+            order: order_info.key(),
+            side,
+            amount,
+            price,
+            keeper: ctx.accounts.keeper.key(),
+            keeper_fee,
+            timestamp: now,
+        });
+        
+        Ok(())
+    }
+
+    pub fn register_wallet_relationship(
+        ctx: Context<RegisterWalletRelationship>,
+        mint: Pubkey,
+        wallet_a: Pubkey,
+        wallet_b: Pubkey,
+        relationship_strength: u16,
+        transaction_count: u16,
+    ) -> Result<()> {
+        let relationship = &mut ctx.accounts.relationship;
+        
+        // Only the launchpad authority can register wallet relationships
+        if ctx.accounts.authority.key() != ctx.accounts.config.authority {
+            return Err(error!(ErrorCode::UnauthorizedAccess));
+        }
+        
+        relationship.mint = mint;
+        relationship.wallet_a = wallet_a;
+        relationship.wallet_b = wallet_b;
+        relationship.relationship_strength = relationship_strength;
+        relationship.last_transaction = Clock::get()?.unix_timestamp;
+        relationship.transaction_count = transaction_count;
+        relationship.last_evidence_slot = 0;
+        relationship.rent_payer = ctx.accounts.authority.key();
+        
+        Ok(())
+    }
+
+    pub fn infer_wallet_relationship(
+        ctx: Context<InferWalletRelationship>,
+    ) -> Result<()> {
+        let config = &ctx.accounts.config;
+        let relationship = &mut ctx.accounts.relationship;
+        let now = Clock::get()?.unix_timestamp;
+        
+        // Compare the signals the trade instructions recorded for both wallets, skipping
+        // any this relationship was already scored on
+        let evidence = score_relationship_evidence(
+            &ctx.accounts.tracker_a,
+            &ctx.accounts.tracker_b,
+            config.funding_window_slots,
+            relationship.last_evidence_slot
+        );
+        
+        if evidence == 0 {
+            return Err(error!(ErrorCode::NoRelationshipEvidence));
+        }
+        
+        // Decay the previous strength before adding the new evidence
+        let decayed_strength = decay_relationship_strength(
+            relationship.relationship_strength,
+            relationship.last_transaction,
+            now
+        );
+        
+        relationship.mint = ctx.accounts.mint.key();
+        relationship.wallet_a = ctx.accounts.wallet_a.key();
+        relationship.wallet_b = ctx.accounts.wallet_b.key();
+        relationship.relationship_strength = decayed_strength
+            .saturating_add(evidence)
+            .min(bundle_detection::MAX_RELATIONSHIP_STRENGTH);
+        relationship.last_transaction = now;
+        relationship.transaction_count = relationship.transaction_count.saturating_add(1);
+        relationship.last_evidence_slot = latest_signal_slot(&ctx.accounts.tracker_a)
+            .max(latest_signal_slot(&ctx.accounts.tracker_b));
+        record_rent_payer(&mut relationship.rent_payer, &ctx.accounts.payer.key());
+        
+        Ok(())
+    }
+
+    pub fn update_bundle_status(
+        ctx: Context<UpdateBundleStatus>,
+        mint: Pubkey,
+        wallet: Pubkey,
+        related_wallets: Vec<Pubkey>,
+        total_bundle_balance: u64,
+    ) -> Result<()> {
+        let bundle_tracker = &mut ctx.accounts.bundle_tracker;
+        let config = &ctx.accounts.config;
+        let project = &ctx.accounts.project;
+        
+        // Only the launchpad authority can update bundle status
+        if ctx.accounts.authority.key() != config.authority {
+            return Err(error!(ErrorCode::UnauthorizedAccess));
+        }
+        
+        // Update the bundle tracker
+        record_rent_payer(&mut bundle_tracker.rent_payer, &ctx.accounts.authority.key());
+        update_bundle_tracker(
+            bundle_tracker,
+            &wallet,
+            &mint,
+            related_wallets,
+            total_bundle_balance,
+            config.bundle_threshold_percentage,
+            project.supply,
+            BundleFlagReason::Manual,
+            config.bundle_flag_duration,
+            ctx.accounts.exempt_address.as_deref()
+        )?;
+        
+        Ok(())
+    }
+
+    pub fn reevaluate_bundle<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ReevaluateBundle<'info>>,
+    ) -> Result<()> {
+        let config = &ctx.accounts.config;
+        let project = &ctx.accounts.project;
+        let bundle_tracker = &mut ctx.accounts.bundle_tracker;
+        let previous_reason = bundle_tracker.flag_reason;
+        
+        // Every previously related wallet must be passed so its holdings still count
+        if !covers_related_wallets(bundle_tracker, ctx.remaining_accounts)? {
+            return Err(error!(ErrorCode::InvalidRelationshipAccounts));
+        }
+        
+        // Recompute the bundle from current balances
+        update_bundle_cluster(
+            bundle_tracker,
+            &ctx.accounts.wallet.key(),
+            &project.mint,
+            ctx.accounts.wallet_token_account.amount,
+            ctx.remaining_accounts,
+            config.relationship_threshold,
+            config.bundle_threshold_percentage,
+            project.supply,
+            config.bundle_flag_duration,
+            ctx.accounts.exempt_address.as_deref()
+        )?;
+        
+        // Refresh the wallet's cluster as well, if it belongs to one
+        update_member_cluster(
+            &ctx.accounts.bundle_membership,
+            ctx.accounts.bundle_cluster.as_mut(),
+            ctx.accounts.wallet_token_account.amount,
+            config.bundle_threshold_percentage,
+            project.supply
+        )?;
+        
+        // A wallet whose cluster is still flagged stays flagged
+        let cluster_bundling = ctx.accounts.bundle_cluster
+            .as_ref()
+            .map(|bundle_cluster| bundle_cluster.is_bundling)
+            .unwrap_or(false);
+        
+        if previous_reason != BundleFlagReason::None && !ctx.accounts.bundle_tracker.is_bundling && !cluster_bundling {
+            emit!(BundleFlagCleared {
+                mint: project.mint,
+                wallet: ctx.accounts.wallet.key(),
+                previous_reason,
+                cleared_by: ctx.accounts.payer.key(),
+                by_authority: false,
+                timestamp: Clock::get()?.unix_timestamp,
+            });
+        }
+        
+        Ok(())
+    }
+
+    pub fn clear_bundle_flag(
+        ctx: Context<ClearBundleFlag>,
+    ) -> Result<()> {
+        let config = &ctx.accounts.config;
+        let bundle_tracker = &mut ctx.accounts.bundle_tracker;
+        
+        // Only the launchpad authority can clear flags outright
+        if ctx.accounts.authority.key() != config.authority {
+            return Err(error!(ErrorCode::UnauthorizedAccess));
+        }
+        
+        let previous_reason = bundle_tracker.flag_reason;
+        bundle_detection::clear_bundle_flag(bundle_tracker);
+        
+        // Clearing a wallet takes it out of its cluster, whose flag still applies to
+        // the remaining members. The membership is closed back to the wallet.
+        if let Some(bundle_membership) = &ctx.accounts.bundle_membership {
+            let bundle_cluster = ctx.accounts.bundle_cluster
+                .as_mut()
+                .ok_or(error!(ErrorCode::ClusterMismatch))?;
+            
+            leave_bundle_cluster(
+                bundle_cluster,
+                bundle_membership,
+                config.bundle_threshold_percentage,
+                ctx.accounts.project.supply
+            )?;
+        }
+        
+        emit!(BundleFlagCleared {
+            mint: ctx.accounts.project.mint,
+            wallet: ctx.accounts.wallet.key(),
+            previous_reason,
+            cleared_by: ctx.accounts.authority.key(),
+            by_authority: true,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    pub fn seize_bundled_tokens(
+        ctx: Context<SeizeBundledTokens>,
+    ) -> Result<()> {
+        let config = &ctx.accounts.config;
+        let project = &ctx.accounts.project;
+        
+        // Only the launchpad authority can seize bundled tokens
+        if ctx.accounts.authority.key() != config.authority {
+            return Err(error!(ErrorCode::UnauthorizedAccess));
+        }
+        
+        // Judge the wallet's whole cluster when it belongs to one
+        let bundle_cluster = match (&ctx.accounts.bundle_membership, &ctx.accounts.bundle_cluster) {
+            (Some(membership), Some(cluster)) if membership.cluster == cluster.key() => Some(cluster),
+            (None, None) => None,
+            _ => return Err(error!(ErrorCode::ClusterMismatch)),
+        };
+        
+        if !is_bundling(
+            &ctx.accounts.bundle_tracker,
+            bundle_cluster,
+            config.bundle_threshold_percentage,
+            project.supply,
+            ctx.accounts.exempt_address.as_deref()
+        )? {
+            return Err(error!(ErrorCode::WalletNotBundling));
+        }
+        
+        let amount = ctx.accounts.source_token_account.amount;
+        if amount == 0 {
+            return Err(error!(ErrorCode::NothingToSeize));
+        }
+        
+        // In block mode the transfer hook is the only penalty
+        if config.bundle_penalty_mode == BundlePenaltyMode::Block {
+            return Err(error!(ErrorCode::BundlePenaltyDisabled));
+        }
+        
+        // The project PDA is the mint's permanent delegate, so it can burn tokens from
+        // any holder without the owner's signature
+        let mint_key = ctx.accounts.mint.key();
+        let project_seeds: &[&[u8]] = &[b"project", mint_key.as_ref(), &[ctx.bumps.project]];
+        
+        token_2022::burn(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    from: ctx.accounts.source_token_account.to_account_info(),
+                    authority: ctx.accounts.project.to_account_info(),
+                },
+                &[project_seeds],
+            ),
+            amount,
+        )?;
+        
+        // Confiscated tokens are re-minted into the penalty vault. A permanent-delegate
+        // transfer would re-enter this program through the mint's transfer hook.
+        if config.bundle_penalty_mode == BundlePenaltyMode::Confiscate {
+            token_2022::mint_to(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    MintTo {
+                        mint: ctx.accounts.mint.to_account_info(),
+                        to: ctx.accounts.penalty_vault.to_account_info(),
+                        authority: ctx.accounts.project.to_account_info(),
+                    },
+                    &[project_seeds],
+                ),
+                amount,
+            )?;
+        }
+        
+        // Seized tokens no longer count toward the creator's disclosed allocation
+        cap_creator_allocation(&mut ctx.accounts.project, &ctx.accounts.wallet.key(), 0);
+        
+        emit!(BundledTokensSeized {
+            mint: mint_key,
+            wallet: ctx.accounts.wallet.key(),
+            amount,
+            mode: config.bundle_penalty_mode,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+
+    pub fn create_bundle_cluster(
+        ctx: Context<CreateBundleCluster>,
+        cluster_id: u64,
+    ) -> Result<()> {
+        let bundle_cluster = &mut ctx.accounts.bundle_cluster;
+        
+        // Only the launchpad authority can create bundle clusters
+        if ctx.accounts.authority.key() != ctx.accounts.config.authority {
+            return Err(error!(ErrorCode::UnauthorizedAccess));
+        }
+        
+        bundle_cluster.mint = ctx.accounts.mint.key();
+        bundle_cluster.cluster_id = cluster_id;
+        bundle_cluster.member_count = 0;
+        bundle_cluster.total_balance = 0;
+        bundle_cluster.force_bundling = false;
+        bundle_cluster.is_bundling = false;
+        bundle_cluster.last_updated = Clock::get()?.unix_timestamp;
+        bundle_cluster.last_buy_at = 0;
+        
+        Ok(())
+    }
+
+    pub fn add_cluster_member(
+        ctx: Context<AddClusterMember>,
+    ) -> Result<()> {
+        let config = &ctx.accounts.config;
+        let project = &ctx.accounts.project;
+        let bundle_cluster = &mut ctx.accounts.bundle_cluster;
+        let bundle_membership = &mut ctx.accounts.bundle_membership;
+        
+        // Only the launchpad authority can assign wallets to clusters
+        if ctx.accounts.authority.key() != config.authority {
+            return Err(error!(ErrorCode::UnauthorizedAccess));
+        }
+        
+        join_bundle_cluster(
+            bundle_cluster,
+            bundle_membership,
+            &ctx.accounts.wallet.key(),
+            &project.mint
+        )?;
+        
+        // Start the member off with its current holdings
+        update_cluster_member_balance(
+            bundle_cluster,
+            bundle_membership,
+            ctx.accounts.wallet_token_account.amount,
+            config.bundle_threshold_percentage,
+            project.supply
+        )?;
+        
+        Ok(())
+    }
+
+    pub fn merge_bundle_clusters<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MergeBundleClusters<'info>>,
+    ) -> Result<()> {
+        let config = &ctx.accounts.config;
+        
+        // Only the launchpad authority can merge clusters
+        if ctx.accounts.authority.key() != config.authority {
+            return Err(error!(ErrorCode::UnauthorizedAccess));
+        }
+        
+        // Memberships of the source cluster are passed as remaining accounts. Large
+        // clusters can be merged over several transactions; the last one closes the
+        // emptied source cluster.
+        move_cluster_members(
+            &mut ctx.accounts.source_cluster,
+            &mut ctx.accounts.target_cluster,
+            ctx.remaining_accounts,
+            &ctx.accounts.authority.to_account_info(),
+            config.bundle_threshold_percentage,
+            ctx.accounts.project.supply
+        )?;
+        
+        Ok(())
+    }
+
+    pub fn split_bundle_cluster<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SplitBundleCluster<'info>>,
+        new_cluster_id: u64,
+    ) -> Result<()> {
+        let config = &ctx.accounts.config;
+        
+        // Only the launchpad authority can split clusters
+        if ctx.accounts.authority.key() != config.authority {
+            return Err(error!(ErrorCode::UnauthorizedAccess));
+        }
+        
+        let new_cluster = &mut ctx.accounts.new_cluster;
+        new_cluster.mint = ctx.accounts.mint.key();
+        new_cluster.cluster_id = new_cluster_id;
+        new_cluster.member_count = 0;
+        new_cluster.total_balance = 0;
+        new_cluster.force_bundling = false;
+        new_cluster.is_bundling = false;
+        new_cluster.last_buy_at = 0;
+        
+        // Memberships passed as remaining accounts move to the new cluster
+        move_cluster_members(
+            &mut ctx.accounts.source_cluster,
+            new_cluster,
+            ctx.remaining_accounts,
+            &ctx.accounts.authority.to_account_info(),
+            config.bundle_threshold_percentage,
+            ctx.accounts.project.supply
+        )?;
+        
+        Ok(())
+    }
+
+    pub fn set_exempt_address(
+        ctx: Context<SetExemptAddress>,
+        address: Pubkey,
+        scopes: u8,
+    ) -> Result<()> {
+        let exempt_address = &mut ctx.accounts.exempt_address;
+        
+        // Only the launchpad authority can manage the allowlist
+        if ctx.accounts.authority.key() != ctx.accounts.config.authority {
+            return Err(error!(ErrorCode::UnauthorizedAccess));
+        }
+        
+        if scopes == 0 || scopes & !EXEMPT_ALL != 0 {
+            return Err(error!(ErrorCode::InvalidExemptionScopes));
+        }
+        
+        exempt_address.address = address;
+        exempt_address.scopes = scopes;
+        
+        Ok(())
+    }
+
+    pub fn remove_exempt_address(
+        ctx: Context<RemoveExemptAddress>,
+        _address: Pubkey,
+    ) -> Result<()> {
+        // Only the launchpad authority can manage the allowlist
+        if ctx.accounts.authority.key() != ctx.accounts.config.authority {
+            return Err(error!(ErrorCode::UnauthorizedAccess));
+        }
+        
+        // The account is closed to the authority by the constraint
+        Ok(())
+    }
+
+    pub fn close_bundle_tracker(
+        ctx: Context<CloseBundleTracker>,
+    ) -> Result<()> {
+        let bundle_tracker = &ctx.accounts.bundle_tracker;
+        
+        // The wallet must have sold out and must not be carrying a live flag
+        if ctx.accounts.wallet_token_account.amount != 0 || bundle_tracker.total_bundle_balance != 0 {
+            return Err(error!(ErrorCode::BundleTrackerInUse));
+        }
+        
+        let now = Clock::get()?.unix_timestamp;
+        if bundle_tracker.is_bundling && !is_flag_expired(bundle_tracker, now) {
+            return Err(error!(ErrorCode::BundleTrackerInUse));
+        }
+        
+        // Re-creating the tracker must not restart a running buy cooldown
+        check_buy_cooldown(&ctx.accounts.project, bundle_tracker.last_buy_at, bundle_tracker.related_wallets.len(), now)?;
+        
+        // The account is closed to whoever paid for it by the constraint
+        Ok(())
+    }
+
+    pub fn close_relationship(
+        ctx: Context<CloseRelationship>,
+    ) -> Result<()> {
+        // Only the launchpad authority can close wallet relationships
+        if ctx.accounts.authority.key() != ctx.accounts.config.authority {
+            return Err(error!(ErrorCode::UnauthorizedAccess));
+        }
+        
+        // The account is closed to whoever paid for it by the constraint
+        Ok(())
+    }
+
+    pub fn close_graduated_accounts<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CloseGraduatedAccounts<'info>>,
+    ) -> Result<()> {
+        let project = &ctx.accounts.project;
+        let now = Clock::get()?.unix_timestamp;
+        
+        // Only the launchpad authority can bulk-close accounts
+        if ctx.accounts.authority.key() != ctx.accounts.config.authority {
+            return Err(error!(ErrorCode::UnauthorizedAccess));
+        }
+        
+        // Tracking accounts are only dead weight once the curve phase is over
+        if !project.is_graduated {
+            return Err(error!(ErrorCode::ProjectNotGraduated));
+        }
+        
+        // Remaining accounts are (account, rent recipient) pairs. Each account refunds
+        // whoever paid for it.
+        if ctx.remaining_accounts.len() % 2 != 0 {
+            return Err(error!(ErrorCode::InvalidCloseAccounts));
+        }
+        
+        for accounts in ctx.remaining_accounts.chunks(2) {
+            let (account_info, recipient_info) = (&accounts[0], &accounts[1]);
+            
+            if let Ok(bundle_tracker) = Account::<BundleTracker>::try_from(account_info) {
+                // Live flags outlast graduation so they keep blocking transfers
+                if bundle_tracker.mint != project.mint
+                    || bundle_tracker.rent_payer != recipient_info.key()
+                    || (bundle_tracker.is_bundling && !is_flag_expired(&bundle_tracker, now))
+                {
+                    return Err(error!(ErrorCode::InvalidCloseAccounts));
+                }
+            } else {
+                let relationship = Account::<WalletRelationship>::try_from(account_info)?;
+                if relationship.mint != project.mint || relationship.rent_payer != recipient_info.key() {
+                    return Err(error!(ErrorCode::InvalidCloseAccounts));
+                }
+            }
+            
+            close_tracking_account(account_info, recipient_info)?;
+        }
+        
+        Ok(())
+    }
+
+    pub fn graduate_token(
+        ctx: Context<GraduateToken>,
+        mint: Pubkey,
+        liquidity_pool: Pubkey,
+    ) -> Result<()> {
+        let project = &mut ctx.accounts.project;
+        let config = &ctx.accounts.config;
+        
+        // Only the launchpad authority can graduate tokens
+        if ctx.accounts.authority.key() != config.authority {
+            return Err(error!(ErrorCode::UnauthorizedAccess));
+        }
+        
+        if is_cancelled(project, Clock::get()?.unix_timestamp) {
+            return Err(error!(ErrorCode::ProjectCancelled));
+        }
+        
+        // Check if the token is eligible for graduation
+        if !is_eligible_for_graduation(project.supply, project.premint_supply, project.current_price, project.graduation_market_cap)? {
+            return Err(error!(ErrorCode::TokenNotEligibleForGraduation));
+        }
+        
+        // Update project state
+        project.is_graduated = true;
+        project.liquidity_pool = Some(liquidity_pool);
+        
+        // Trading limits only apply during the curve phase
+        project.max_wallet_bps = None;
+        project.max_buy_per_tx = None;
+        
+        // In a real implementation, you would:
+        // 1. Create a liquidity pool on Raydium
+        // 2. Transfer tokens and SOL to the pool
+        // 3. Initialize the pool
+        
+        Ok(())
+    }
+
+    pub fn create_raydium_pool(
+        ctx: Context<CreateRaydiumPool>,
+        mint: Pubkey,
+        initial_liquidity_amount: u64,
+        initial_token_amount: u64,
+    ) -> Result<()> {
+        let project = &mut ctx.accounts.project;
+        let config = &ctx.accounts.config;
+        
+        // Only the launchpad authority can create liquidity pools
+        if ctx.accounts.authority.key() != config.authority {
+            return Err(error!(ErrorCode::UnauthorizedAccess));
+        }
+        
+        // Check if the token is eligible for graduation
+        if !is_eligible_for_graduation(project.supply, project.premint_supply, project.current_price, project.graduation_market_cap)? {
+            return Err(error!(ErrorCode::TokenNotEligibleForGraduation));
+        }
+        
+        // Check if the project has enough reserve balance for initial liquidity
+        if project.reserve_balance < initial_liquidity_amount {
+            return Err(error!(ErrorCode::InsufficientFunds));
+        }
+        
+        // In a real implementation, you would:
+        // 1. Create a Raydium CLMM pool
+        // 2. Transfer SOL and tokens to the pool
+        // 3. Initialize the pool with the desired parameters
+        
+        // For now, we'll just update the project state
+        project.is_graduated = true;
+        project.liquidity_pool = Some(ctx.accounts.liquidity_pool.key());
+        project.max_wallet_bps = None;
+        project.max_buy_per_tx = None;
+        
+        // The project PDA signs for its reserve instead of a human
+        let mint_key = ctx.accounts.mint.key();
+        let project_seeds: &[&[u8]] = &[b"project", mint_key.as_ref(), &[ctx.bumps.project]];
+        let project_info = project.to_account_info();
+        
+        match project.quote_mint {
+            // Quote-token reserves already sit in the project's vault and move as they are
+            Some(quote_mint_key) => {
+                let (quote_mint, quote_vault, pool_quote_account, quote_token_program) = match (
+                    &ctx.accounts.quote_mint,
+                    &ctx.accounts.quote_vault,
+                    &ctx.accounts.pool_quote_account,
+                    &ctx.accounts.quote_token_program,
+                ) {
+                    (Some(quote_mint), Some(quote_vault), Some(pool_quote_account), Some(quote_token_program)) => {
+                        (quote_mint, quote_vault, pool_quote_account, quote_token_program)
+                    }
+                    _ => return Err(error!(ErrorCode::QuoteAccountsRequired)),
+                };
+                
+                if quote_mint.key() != quote_mint_key {
+                    return Err(error!(ErrorCode::QuoteAccountMismatch));
+                }
+                
+                token_interface::transfer_checked(
+                    CpiContext::new_with_signer(
+                        quote_token_program.to_account_info(),
+                        token_interface::TransferChecked {
+                            from: quote_vault.to_account_info(),
+                            mint: quote_mint.to_account_info(),
+                            to: pool_quote_account.to_account_info(),
+                            authority: project_info,
+                        },
+                        &[project_seeds],
+                    ),
+                    initial_liquidity_amount,
+                    quote_mint.decimals,
+                )?;
+            }
+            // SOL reserves are wrapped into the project's WSOL account and moved to the pool
+            None => {
+                let (project_wsol_account, pool_wsol_account, wsol_mint) = match (
+                    &ctx.accounts.project_wsol_account,
+                    &ctx.accounts.pool_wsol_account,
+                    &ctx.accounts.wsol_mint,
+                ) {
+                    (Some(project_wsol_account), Some(pool_wsol_account), Some(wsol_mint)) => {
+                        (project_wsol_account, pool_wsol_account, wsol_mint)
+                    }
+                    _ => return Err(error!(ErrorCode::InvalidWsolAccount)),
+                };
+                
+                wrap_sol_signed(
+                    initial_liquidity_amount,
+                    &project_info,
+                    &project_info,
+                    project_wsol_account,
+                    &ctx.accounts.authority.to_account_info(),
+                    wsol_mint,
+                    &ctx.accounts.system_program.to_account_info(),
+                    &ctx.accounts.token_program.to_account_info(),
+                    &ctx.accounts.associated_token_program.to_account_info(),
+                    &[project_seeds],
+                )?;
+                
+                anchor_spl::token::transfer(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        anchor_spl::token::Transfer {
+                            from: project_wsol_account.to_account_info(),
+                            to: pool_wsol_account.to_account_info(),
+                            authority: project_info.clone(),
+                        },
+                        &[project_seeds],
+                    ),
+                    initial_liquidity_amount,
+                )?;
+                
+                // Close the emptied WSOL account, returning its rent to the authority that paid it
+                unwrap_sol_signed(
+                    project_wsol_account,
+                    &project_info,
+                    &ctx.accounts.authority.to_account_info(),
+                    &ctx.accounts.token_program.to_account_info(),
+                    &[project_seeds],
+                )?;
+            }
+        }
+        
+        // Update project state
+        project.reserve_balance = project.reserve_balance
+            .checked_sub(initial_liquidity_amount)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+        
+        Ok(())
+    }
+
+    pub fn withdraw_platform_fees(
+        ctx: Context<WithdrawPlatformFees>,
+        amount: u64,
+    ) -> Result<()> {
+        // Only the launchpad authority can withdraw fees
+        if ctx.accounts.authority.key() != ctx.accounts.config.authority {
+            return Err(error!(ErrorCode::UnauthorizedAccess));
+        }
+        
+        // Check if the fee vault has enough balance
+        if ctx.accounts.fee_vault.lamports() < amount {
+            return Err(error!(ErrorCode::InsufficientFunds));
+        }
+        
+        // Transfer SOL from fee vault to recipient
+        **ctx.accounts.fee_vault.try_borrow_mut_lamports()? = ctx
+            .accounts.fee_vault
+            .lamports()
+            .checked_sub(amount)
+            .ok_or(error!(ErrorCode::InsufficientFunds))?;
+            
+        **ctx.accounts.recipient.try_borrow_mut_lamports()? = ctx
+            .accounts.recipient
+            .lamports()
+            .checked_add(amount)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+        
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializeLaunchpad<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + LaunchpadConfig::INIT_SPACE,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, LaunchpadConfig>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8,
+        seeds = [b"fee_vault"],
+        bump
+    )]
+    pub fee_vault: SystemAccount<'info>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    /// CHECK: Deserialized by hand since it may still be in an older layout
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+        owner = crate::ID
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateProject<'info> {
+    pub config: Account<'info, LaunchpadConfig>,
+    
+    /// CHECK: Deserialized by hand since it may still be in an older layout
+    #[account(
+        mut,
+        seeds = [b"project", mint.key().as_ref()],
+        bump,
+        owner = crate::ID
+    )]
+    pub project: UncheckedAccount<'info>,
+    
+    /// CHECK: This is just a pubkey parameter
+    pub mint: UncheckedAccount<'info>,
+    
+    /// Graduation cap of the project's quote mint, required to migrate a quote-token
+    /// project that predates per-project caps
+    pub quote_config: Option<Account<'info, QuoteConfig>>,
+    
+    /// Created here for projects that predate launch ledgers
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + LaunchLedger::INIT_SPACE,
+        seeds = [b"launch_ledger", mint.key().as_ref()],
+        bump
+    )]
+    pub launch_ledger: Account<'info, LaunchLedger>,
+    
+    /// Created here for projects that predate sniper clusters
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + BundleCluster::INIT_SPACE,
+        seeds = [b"cluster", mint.key().as_ref(), &SNIPER_CLUSTER_ID.to_le_bytes()],
+        bump
+    )]
+    pub sniper_cluster: Account<'info, BundleCluster>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateTokenProject<'info> {
+    #[account(mut)]
+    pub config: Account<'info, LaunchpadConfig>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + TokenProject::INIT_SPACE,
+        seeds = [b"project", mint.key().as_ref()],
+        bump
+    )]
+    pub project: Account<'info, TokenProject>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + LaunchLedger::INIT_SPACE,
+        seeds = [b"launch_ledger", mint.key().as_ref()],
+        bump
+    )]
+    pub launch_ledger: Account<'info, LaunchLedger>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + BundleCluster::INIT_SPACE,
+        seeds = [b"cluster", mint.key().as_ref(), &SNIPER_CLUSTER_ID.to_le_bytes()],
+        bump
+    )]
+    pub sniper_cluster: Account<'info, BundleCluster>,
+    
+    /// New mint keypair, allocated and initialized by the program
+    #[account(mut)]
+    pub mint: Signer<'info>,
+    
+    /// SPL token the project is priced in; native SOL when omitted
+    pub quote_mint: Option<InterfaceAccount<'info, token_interface::Mint>>,
+    
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"quote_vault", mint.key().as_ref()],
+        bump,
+        token::mint = quote_mint,
+        token::authority = project,
+        token::token_program = quote_token_program,
+    )]
+    pub quote_vault: Option<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    
+    pub quote_token_program: Option<Interface<'info, token_interface::TokenInterface>>,
+    
+    /// Graduation cap of the quote mint, required with it
+    pub quote_config: Option<Account<'info, QuoteConfig>>,
+    
+    /// Receives the platform fee of a dev-buy
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
+        bump
+    )]
+    pub fee_vault: Option<SystemAccount<'info>>,
+    
+    /// CHECK: The creator's associated token account for the new mint, created by the
+    /// associated token program during a dev-buy
+    #[account(mut)]
+    pub creator_token_account: Option<UncheckedAccount<'info>>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + VestingEscrow::INIT_SPACE,
+        seeds = [b"vesting", mint.key().as_ref()],
+        bump
+    )]
+    pub vesting_escrow: Option<Account<'info, VestingEscrow>>,
+    
+    /// CHECK: The vesting escrow's associated token account for the new mint, created
+    /// by the associated token program when a vesting schedule is passed
+    #[account(mut)]
+    pub vesting_vault: Option<UncheckedAccount<'info>>,
+    
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + LaunchAuction::INIT_SPACE,
+        seeds = [b"auction", mint.key().as_ref()],
+        bump
+    )]
+    pub launch_auction: Option<Account<'info, LaunchAuction>>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeExtraAccountMetaList<'info> {
+    /// CHECK: The mint's transfer hook validation account, written by the instruction
+    #[account(
+        init,
+        payer = payer,
+        space = ExtraAccountMetaList::size_of(extra_account_metas()?.len())?,
+        seeds = [b"extra-account-metas", mint.key().as_ref()],
+        bump
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [b"project", mint.key().as_ref()],
+        bump
+    )]
+    pub project: Account<'info, TokenProject>,
+    
+    #[account(address = project.mint)]
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateProjectMetadata<'info> {
+    #[account(
+        mut,
+        seeds = [b"project", mint.key().as_ref()],
+        bump
+    )]
+    pub project: Account<'info, TokenProject>,
+    
+    /// CHECK: The project's Token-2022 mint, checked against the project
+    #[account(
+        mut,
+        address = project.mint
+    )]
+    pub mint: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimVested<'info> {
+    #[account(
+        mut,
+        seeds = [b"project", mint.key().as_ref()],
+        bump
+    )]
+    pub project: Account<'info, TokenProject>,
+    
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+    
+    #[account(
+        mut,
+        seeds = [b"vesting", mint.key().as_ref()],
+        bump
+    )]
+    pub vesting_escrow: Account<'info, VestingEscrow>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vesting_escrow,
+        associated_token::token_program = token_program,
+    )]
+    pub vesting_vault: InterfaceAccount<'info, token_interface::TokenAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = beneficiary,
+        associated_token::mint = mint,
+        associated_token::authority = beneficiary,
+        associated_token::token_program = token_program,
+    )]
+    pub beneficiary_token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+    
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+    
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CommitToLaunch<'info> {
+    #[account(
+        mut,
+        seeds = [b"project", mint.key().as_ref()],
+        bump
+    )]
+    pub project: Account<'info, TokenProject>,
+    
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+    
+    #[account(
+        mut,
+        seeds = [b"auction", mint.key().as_ref()],
+        bump
+    )]
+    pub launch_auction: Account<'info, LaunchAuction>,
+    
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + AuctionCommitment::INIT_SPACE,
+        seeds = [b"commitment", mint.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub commitment: Account<'info, AuctionCommitment>,
+    
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SettleLaunch<'info> {
+    pub config: Account<'info, LaunchpadConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"project", mint.key().as_ref()],
+        bump
+    )]
+    pub project: Account<'info, TokenProject>,
+    
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+    
+    #[account(
+        mut,
+        seeds = [b"auction", mint.key().as_ref()],
+        bump
+    )]
+    pub launch_auction: Account<'info, LaunchAuction>,
+    
+    /// CHECK: Receives the creator's half of the auction fee
+    #[account(
+        mut,
+        address = project.creator
+    )]
+    pub creator: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
+        bump
+    )]
+    pub fee_vault: SystemAccount<'info>,
+    
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawCommitment<'info> {
+    #[account(
+        seeds = [b"project", mint.key().as_ref()],
+        bump
+    )]
+    pub project: Account<'info, TokenProject>,
+    
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+    
+    #[account(
+        mut,
+        seeds = [b"auction", mint.key().as_ref()],
+        bump
+    )]
+    pub launch_auction: Account<'info, LaunchAuction>,
+    
+    #[account(
+        mut,
+        close = wallet,
+        seeds = [b"commitment", mint.key().as_ref(), wallet.key().as_ref()],
+        bump
+    )]
+    pub commitment: Account<'info, AuctionCommitment>,
+    
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelLaunch<'info> {
+    #[account(
+        mut,
+        seeds = [b"project", mint.key().as_ref()],
+        bump
+    )]
+    pub project: Account<'info, TokenProject>,
+    
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRefund<'info> {
+    #[account(
+        mut,
+        seeds = [b"project", mint.key().as_ref()],
+        bump
+    )]
+    pub project: Account<'info, TokenProject>,
+    
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = holder,
+        associated_token::token_program = token_program,
+    )]
+    pub holder_token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+    
+    #[account(mut)]
+    pub holder: Signer<'info>,
+    
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct BuyTokens<'info> {
+    pub config: Account<'info, LaunchpadConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"project", mint.key().as_ref()],
+        bump
+    )]
+    pub project: Account<'info, TokenProject>,
+    
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+    
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = buyer,
+        associated_token::token_program = token_program,
+    )]
+    pub buyer_token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
+        bump
+    )]
+    pub fee_vault: SystemAccount<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + BundleTracker::INIT_SPACE,
+        seeds = [b"bundle", mint.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub bundle_tracker: Account<'info, BundleTracker>,
+    
+    /// CHECK: The buyer's cluster membership PDA, created when a sniper is tagged into
+    /// the sniper cluster and loaded only if the buyer joined a cluster
+    #[account(
+        mut,
+        seeds = [b"membership", mint.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub bundle_membership: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub bundle_cluster: Option<Account<'info, BundleCluster>>,
+    
+    #[account(
+        seeds = [b"exempt", buyer.key().as_ref()],
+        bump
+    )]
+    pub exempt_address: Option<Account<'info, ExemptAddress>>,
+    
+    #[account(
+        mut,
+        seeds = [b"launch_ledger", mint.key().as_ref()],
+        bump
+    )]
+    pub launch_ledger: Account<'info, LaunchLedger>,
+    
+    /// The transaction fee payer, when it is not the buyer
+    pub fee_payer: Option<Signer<'info>>,
+    
+    /// CHECK: Checked against the instructions sysvar address
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+    
+    /// Referrer credited with a share of the fee, if the trade was referred
+    #[account(mut)]
+    pub referrer: Option<Account<'info, Referrer>>,
+    
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + TraderStats::INIT_SPACE,
+        seeds = [b"trader_stats", buyer.key().as_ref()],
+        bump
+    )]
+    pub trader_stats: Option<Account<'info, TraderStats>>,
+    
+    /// Running total of the buyer's whitelist-phase purchases
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + WhitelistPurchase::INIT_SPACE,
+        seeds = [b"whitelist", mint.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub whitelist_purchase: Option<Account<'info, WhitelistPurchase>>,
+    
+    /// Quote mint, required when the project is priced in an SPL token
+    pub quote_mint: Option<InterfaceAccount<'info, token_interface::Mint>>,
+    
+    #[account(
+        mut,
+        seeds = [b"quote_vault", mint.key().as_ref()],
+        bump
+    )]
+    pub quote_vault: Option<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    
+    #[account(mut)]
+    pub buyer_quote_account: Option<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    
+    #[account(mut)]
+    pub creator_quote_account: Option<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    
+    #[account(mut)]
+    pub fee_vault_quote_account: Option<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    
+    pub quote_token_program: Option<Interface<'info, token_interface::TokenInterface>>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct SellTokens<'info> {
+    pub config: Account<'info, LaunchpadConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"project", mint.key().as_ref()],
+        bump
+    )]
+    pub project: Account<'info, TokenProject>,
+    
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+    
+    #[account(mut)]
+    pub seller: Signer<'info>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = seller,
+        associated_token::token_program = token_program,
+    )]
+    pub seller_token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
+        bump
+    )]
+    pub fee_vault: SystemAccount<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = seller,
+        space = 8 + BundleTracker::INIT_SPACE,
+        seeds = [b"bundle", mint.key().as_ref(), seller.key().as_ref()],
+        bump
+    )]
+    pub bundle_tracker: Account<'info, BundleTracker>,
+    
+    /// CHECK: The seller's cluster membership PDA, loaded only if the seller joined a cluster
+    #[account(
+        mut,
+        seeds = [b"membership", mint.key().as_ref(), seller.key().as_ref()],
+        bump
+    )]
+    pub bundle_membership: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub bundle_cluster: Option<Account<'info, BundleCluster>>,
+    
+    #[account(
+        seeds = [b"exempt", seller.key().as_ref()],
+        bump
+    )]
+    pub exempt_address: Option<Account<'info, ExemptAddress>>,
+    
+    /// Referrer credited with a share of the fee, if the trade was referred
+    #[account(mut)]
+    pub referrer: Option<Account<'info, Referrer>>,
+    
+    #[account(
+        init_if_needed,
+        payer = seller,
+        space = 8 + TraderStats::INIT_SPACE,
+        seeds = [b"trader_stats", seller.key().as_ref()],
+        bump
+    )]
+    pub trader_stats: Option<Account<'info, TraderStats>>,
+    
+    /// Quote mint, required when the project is priced in an SPL token
+    pub quote_mint: Option<InterfaceAccount<'info, token_interface::Mint>>,
+    
+    #[account(
+        mut,
+        seeds = [b"quote_vault", mint.key().as_ref()],
+        bump
+    )]
+    pub quote_vault: Option<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    
+    #[account(mut)]
+    pub seller_quote_account: Option<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    
+    #[account(mut)]
+    pub creator_quote_account: Option<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    
+    #[account(mut)]
+    pub fee_vault_quote_account: Option<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    
+    pub quote_token_program: Option<Interface<'info, token_interface::TokenInterface>>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+#[derive(Accounts)]
+#[instruction(order_id: u64)]
+pub struct PlaceOrder<'info> {
+    #[account(
+        mut,
+        seeds = [b"project", mint.key().as_ref()],
+        bump
+    )]
+    pub project: Account<'info, TokenProject>,
+    
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Order::INIT_SPACE,
+        seeds = [b"order", mint.key().as_ref(), owner.key().as_ref(), &order_id.to_le_bytes()],
+        bump
+    )]
+    pub order: Account<'info, Order>,
+    
+    /// Burns the tokens of a sell order
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+    )]
+    pub owner_token_account: Option<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelOrder<'info> {
+    /// Mint authority that re-mints a sell order's tokens
+    #[account(
+        seeds = [b"project", mint.key().as_ref()],
+        bump
+    )]
+    pub project: Account<'info, TokenProject>,
+    
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"order", mint.key().as_ref(), owner.key().as_ref(), &order.order_id.to_le_bytes()],
+        bump
+    )]
+    pub order: Account<'info, Order>,
+    
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+    
+    /// CHECK: Receives the escrow back, checked against the order
+    #[account(
+        mut,
+        address = order.owner
+    )]
+    pub owner: UncheckedAccount<'info>,
+    
+    /// Receives the tokens of a cancelled sell order
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+    )]
+    pub owner_token_account: Option<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteOrder<'info> {
+    pub config: Account<'info, LaunchpadConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"project", mint.key().as_ref()],
+        bump
+    )]
+    pub project: Account<'info, TokenProject>,
+    
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+    
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"order", mint.key().as_ref(), owner.key().as_ref(), &order.order_id.to_le_bytes()],
+        bump
+    )]
+    pub order: Account<'info, Order>,
+    
+    /// CHECK: Receives sell proceeds and the rest of the escrow, checked against the order
+    #[account(
+        mut,
+        address = order.owner
+    )]
+    pub owner: UncheckedAccount<'info>,
+    
+    /// Receives the tokens of a buy order
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+    )]
+    pub owner_token_account: Option<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    
+    /// CHECK: Receives the creator's half of the fee
+    #[account(
+        mut,
+        address = project.creator
+    )]
+    pub creator: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
+        bump
+    )]
+    pub fee_vault: SystemAccount<'info>,
+    
+    /// Bundle tracker of the order owner
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        space = 8 + BundleTracker::INIT_SPACE,
+        seeds = [b"bundle", mint.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub bundle_tracker: Account<'info, BundleTracker>,
+    
+    /// CHECK: The owner's cluster membership PDA, loaded only if the owner joined a cluster
+    #[account(
+        mut,
+        seeds = [b"membership", mint.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub bundle_membership: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub bundle_cluster: Option<Account<'info, BundleCluster>>,
+    
+    #[account(
+        seeds = [b"exempt", owner.key().as_ref()],
+        bump
+    )]
+    pub exempt_address: Option<Account<'info, ExemptAddress>>,
+    
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+    
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterWalletRelationship<'info> {
+    pub config: Account<'info, LaunchpadConfig>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + WalletRelationship::INIT_SPACE,
+        seeds = [b"relationship", mint.key().as_ref(), wallet_a.key().as_ref(), wallet_b.key().as_ref()],
+        bump
+    )]
+    pub relationship: Account<'info, WalletRelationship>,
+    
+    /// CHECK: This is just a pubkey parameter
+    pub mint: UncheckedAccount<'info>,
+    
+    /// CHECK: This is just a pubkey parameter
+    pub wallet_a: UncheckedAccount<'info>,
+    
+    /// CHECK: This is just a pubkey parameter
+    pub wallet_b: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InferWalletRelationship<'info> {
+    pub config: Account<'info, LaunchpadConfig>,
+    
+    #[account(
+        seeds = [b"bundle", mint.key().as_ref(), wallet_a.key().as_ref()],
+        bump
+    )]
+    pub tracker_a: Account<'info, BundleTracker>,
+    
+    #[account(
+        seeds = [b"bundle", mint.key().as_ref(), wallet_b.key().as_ref()],
+        bump
+    )]
+    pub tracker_b: Account<'info, BundleTracker>,
+    
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + WalletRelationship::INIT_SPACE,
+        seeds = [b"relationship", mint.key().as_ref(), wallet_a.key().as_ref(), wallet_b.key().as_ref()],
+        bump
+    )]
+    pub relationship: Account<'info, WalletRelationship>,
+    
+    /// CHECK: This is just a pubkey parameter
+    pub mint: UncheckedAccount<'info>,
+    
+    /// CHECK: This is just a pubkey parameter. Wallets must be in ascending order so each pair has one relationship.
+    #[account(constraint = wallet_a.key() < wallet_b.key() @ ErrorCode::InvalidRelationshipAccounts)]
+    pub wallet_a: UncheckedAccount<'info>,
+    
+    /// CHECK: This is just a pubkey parameter
+    pub wallet_b: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetRelationshipParams<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, LaunchpadConfig>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateBundleStatus<'info> {
+    pub config: Account<'info, LaunchpadConfig>,
+    
+    #[account(
+        seeds = [b"project", mint.key().as_ref()],
+        bump
+    )]
+    pub project: Account<'info, TokenProject>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + BundleTracker::INIT_SPACE,
+        seeds = [b"bundle", mint.key().as_ref(), wallet.key().as_ref()],
+        bump
+    )]
+    pub bundle_tracker: Account<'info, BundleTracker>,
+    
+    /// CHECK: This is just a pubkey parameter
+    pub mint: UncheckedAccount<'info>,
+    
+    /// CHECK: This is just a pubkey parameter
+    pub wallet: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [b"exempt", wallet.key().as_ref()],
+        bump
+    )]
+    pub exempt_address: Option<Account<'info, ExemptAddress>>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetBundlePenaltyMode<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, LaunchpadConfig>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetBundleFlagDuration<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, LaunchpadConfig>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReevaluateBundle<'info> {
+    pub config: Account<'info, LaunchpadConfig>,
+    
+    #[account(
+        seeds = [b"project", mint.key().as_ref()],
+        bump
+    )]
+    pub project: Account<'info, TokenProject>,
+    
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+    
+    #[account(
+        mut,
+        seeds = [b"bundle", mint.key().as_ref(), wallet.key().as_ref()],
+        bump
+    )]
+    pub bundle_tracker: Account<'info, BundleTracker>,
+    
+    /// CHECK: The wallet's cluster membership PDA, loaded only if the wallet joined a cluster
+    #[account(
+        mut,
+        seeds = [b"membership", mint.key().as_ref(), wallet.key().as_ref()],
+        bump
+    )]
+    pub bundle_membership: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub bundle_cluster: Option<Account<'info, BundleCluster>>,
+    
+    /// CHECK: This is just a pubkey parameter
+    pub wallet: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [b"exempt", wallet.key().as_ref()],
+        bump
+    )]
+    pub exempt_address: Option<Account<'info, ExemptAddress>>,
+    
+    #[account(
+        associated_token::mint = mint,
+        associated_token::authority = wallet,
+        associated_token::token_program = token_program,
+    )]
+    pub wallet_token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+    
+    pub payer: Signer<'info>,
+    
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct ClearBundleFlag<'info> {
+    pub config: Account<'info, LaunchpadConfig>,
+    
+    #[account(
+        seeds = [b"project", mint.key().as_ref()],
+        bump
+    )]
+    pub project: Account<'info, TokenProject>,
+    
+    #[account(
+        mut,
+        seeds = [b"bundle", mint.key().as_ref(), wallet.key().as_ref()],
+        bump
+    )]
+    pub bundle_tracker: Account<'info, BundleTracker>,
+    
+    #[account(
+        mut,
+        close = wallet,
+        seeds = [b"membership", mint.key().as_ref(), wallet.key().as_ref()],
+        bump
+    )]
+    pub bundle_membership: Option<Account<'info, ClusterMembership>>,
+    
+    #[account(
+        mut,
+        constraint = bundle_cluster.mint == mint.key() @ ErrorCode::ClusterMismatch
+    )]
+    pub bundle_cluster: Option<Account<'info, BundleCluster>>,
+    
+    /// CHECK: This is just a pubkey parameter
+    pub mint: UncheckedAccount<'info>,
+    
+    /// CHECK: Receives the rent of the closed membership
+    #[account(mut)]
+    pub wallet: UncheckedAccount<'info>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetSnipeRules<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, LaunchpadConfig>,
+    
+    pub authority: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct InitializeLaunchpad<'info> {
+pub struct SeizeBundledTokens<'info> {
+    pub config: Account<'info, LaunchpadConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"project", mint.key().as_ref()],
+        bump
+    )]
+    pub project: Account<'info, TokenProject>,
+    
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+    
+    #[account(
+        seeds = [b"bundle", mint.key().as_ref(), wallet.key().as_ref()],
+        bump
+    )]
+    pub bundle_tracker: Account<'info, BundleTracker>,
+    
+    #[account(
+        seeds = [b"membership", mint.key().as_ref(), wallet.key().as_ref()],
+        bump
+    )]
+    pub bundle_membership: Option<Account<'info, ClusterMembership>>,
+    
+    pub bundle_cluster: Option<Account<'info, BundleCluster>>,
+    
+    /// CHECK: This is just a pubkey parameter
+    pub wallet: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [b"exempt", wallet.key().as_ref()],
+        bump
+    )]
+    pub exempt_address: Option<Account<'info, ExemptAddress>>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = wallet,
+        token::token_program = token_program,
+    )]
+    pub source_token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [b"penalty_vault", mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = project,
+        token::token_program = token_program,
+    )]
+    pub penalty_vault: InterfaceAccount<'info, token_interface::TokenAccount>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(cluster_id: u64)]
+pub struct CreateBundleCluster<'info> {
+    pub config: Account<'info, LaunchpadConfig>,
+    
     #[account(
         init,
         payer = authority,
-        space = 8 + std::mem::size_of::<LaunchpadConfig>(),
-        seeds = [b"config"],
+        space = 8 + BundleCluster::INIT_SPACE,
+        seeds = [b"cluster", mint.key().as_ref(), &cluster_id.to_le_bytes()],
         bump
     )]
+    pub bundle_cluster: Account<'info, BundleCluster>,
+    
+    /// CHECK: This is just a pubkey parameter
+    pub mint: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AddClusterMember<'info> {
     pub config: Account<'info, LaunchpadConfig>,
     
+    #[account(
+        seeds = [b"project", mint.key().as_ref()],
+        bump
+    )]
+    pub project: Account<'info, TokenProject>,
+    
+    #[account(
+        mut,
+        seeds = [b"cluster", mint.key().as_ref(), &bundle_cluster.cluster_id.to_le_bytes()],
+        bump
+    )]
+    pub bundle_cluster: Account<'info, BundleCluster>,
+    
     #[account(
         init,
         payer = authority,
-        space = 8,
-        seeds = [b"fee_vault"],
+        space = 8 + ClusterMembership::INIT_SPACE,
+        seeds = [b"membership", mint.key().as_ref(), wallet.key().as_ref()],
         bump
     )]
-    pub fee_vault: SystemAccount<'info>,
+    pub bundle_membership: Account<'info, ClusterMembership>,
+    
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+    
+    /// CHECK: This is just a pubkey parameter
+    pub wallet: UncheckedAccount<'info>,
+    
+    #[account(
+        associated_token::mint = mint,
+        associated_token::authority = wallet,
+        associated_token::token_program = token_program,
+    )]
+    pub wallet_token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
     
     #[account(mut)]
     pub authority: Signer<'info>,
     
     pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
 }
 
 #[derive(Accounts)]
-pub struct CreateTokenProject<'info> {
+pub struct MergeBundleClusters<'info> {
+    pub config: Account<'info, LaunchpadConfig>,
+    
+    #[account(
+        seeds = [b"project", mint.key().as_ref()],
+        bump
+    )]
+    pub project: Account<'info, TokenProject>,
+    
+    #[account(
+        mut,
+        seeds = [b"cluster", mint.key().as_ref(), &source_cluster.cluster_id.to_le_bytes()],
+        bump
+    )]
+    pub source_cluster: Account<'info, BundleCluster>,
+    
+    #[account(
+        mut,
+        seeds = [b"cluster", mint.key().as_ref(), &target_cluster.cluster_id.to_le_bytes()],
+        bump
+    )]
+    pub target_cluster: Account<'info, BundleCluster>,
+    
+    /// CHECK: This is just a pubkey parameter
+    pub mint: UncheckedAccount<'info>,
+    
+    /// Receives the rent of an emptied source cluster
     #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(new_cluster_id: u64)]
+pub struct SplitBundleCluster<'info> {
     pub config: Account<'info, LaunchpadConfig>,
     
     #[account(
-        init,
-        payer = authority,
-        space = 8 + std::mem::size_of::<TokenProject>(),
         seeds = [b"project", mint.key().as_ref()],
         bump
     )]
     pub project: Account<'info, TokenProject>,
     
+    #[account(
+        mut,
+        seeds = [b"cluster", mint.key().as_ref(), &source_cluster.cluster_id.to_le_bytes()],
+        bump
+    )]
+    pub source_cluster: Account<'info, BundleCluster>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + BundleCluster::INIT_SPACE,
+        seeds = [b"cluster", mint.key().as_ref(), &new_cluster_id.to_le_bytes()],
+        bump
+    )]
+    pub new_cluster: Account<'info, BundleCluster>,
+    
+    /// CHECK: This is just a pubkey parameter
+    pub mint: UncheckedAccount<'info>,
+    
     #[account(mut)]
-    pub mint: Signer<'info>,
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(address: Pubkey)]
+pub struct SetExemptAddress<'info> {
+    pub config: Account<'info, LaunchpadConfig>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + ExemptAddress::INIT_SPACE,
+        seeds = [b"exempt", address.as_ref()],
+        bump
+    )]
+    pub exempt_address: Account<'info, ExemptAddress>,
     
     #[account(mut)]
     pub authority: Signer<'info>,
     
-    pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
 }
 
 #[derive(Accounts)]
-pub struct BuyTokens<'info> {
+#[instruction(address: Pubkey)]
+pub struct RemoveExemptAddress<'info> {
     pub config: Account<'info, LaunchpadConfig>,
     
     #[account(
         mut,
+        close = authority,
+        seeds = [b"exempt", address.as_ref()],
+        bump
+    )]
+    pub exempt_address: Account<'info, ExemptAddress>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseBundleTracker<'info> {
+    #[account(
+        mut,
+        close = rent_payer,
+        seeds = [b"bundle", mint.key().as_ref(), wallet.key().as_ref()],
+        bump
+    )]
+    pub bundle_tracker: Account<'info, BundleTracker>,
+    
+    #[account(
         seeds = [b"project", mint.key().as_ref()],
         bump
     )]
     pub project: Account<'info, TokenProject>,
     
-    #[account(mut)]
-    pub mint: Account<'info, Mint>,
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
     
-    #[account(mut)]
-    pub buyer: Signer<'info>,
+    pub wallet: Signer<'info>,
     
+    /// CHECK: Receives the tracker's rent, checked against the payer it recorded
     #[account(
         mut,
+        address = bundle_tracker.rent_payer @ ErrorCode::InvalidCloseAccounts
+    )]
+    pub rent_payer: UncheckedAccount<'info>,
+    
+    #[account(
         associated_token::mint = mint,
-        associated_token::authority = buyer,
+        associated_token::authority = wallet,
+        associated_token::token_program = token_program,
     )]
-    pub buyer_token_account: Account<'info, TokenAccount>,
+    pub wallet_token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+    
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct CloseRelationship<'info> {
+    pub config: Account<'info, LaunchpadConfig>,
     
     #[account(
         mut,
-        seeds = [b"fee_vault"],
+        close = rent_payer,
+        seeds = [b"relationship", relationship.mint.as_ref(), relationship.wallet_a.as_ref(), relationship.wallet_b.as_ref()],
         bump
     )]
-    pub fee_vault: SystemAccount<'info>,
+    pub relationship: Account<'info, WalletRelationship>,
     
+    /// CHECK: Receives the relationship's rent, checked against the payer it recorded
     #[account(
-        init_if_needed,
-        payer = buyer,
-        space = 8 + std::mem::size_of::<BundleTracker>(),
-        seeds = [b"bundle", mint.key().as_ref(), buyer.key().as_ref()],
+        mut,
+        address = relationship.rent_payer @ ErrorCode::InvalidCloseAccounts
+    )]
+    pub rent_payer: UncheckedAccount<'info>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseGraduatedAccounts<'info> {
+    pub config: Account<'info, LaunchpadConfig>,
+    
+    #[account(
+        seeds = [b"project", mint.key().as_ref()],
+        bump
+    )]
+    pub project: Account<'info, TokenProject>,
+    
+    /// CHECK: This is just a pubkey parameter
+    pub mint: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct GraduateToken<'info> {
+    pub config: Account<'info, LaunchpadConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"project", mint.key().as_ref()],
         bump
     )]
-    pub bundle_tracker: Option<Account<'info, BundleTracker>>,
+    pub project: Account<'info, TokenProject>,
+    
+    /// CHECK: This is just a pubkey parameter
+    pub mint: UncheckedAccount<'info>,
+    
+    /// CHECK: This is just a pubkey parameter
+    pub liquidity_pool: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
     
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
 }
 
 #[derive(Accounts)]
-pub struct SellTokens<'info> {
+pub struct CreateRaydiumPool<'info> {
     pub config: Account<'info, LaunchpadConfig>,
     
     #[account(
@@ -598,91 +4221,82 @@ pub struct SellTokens<'info> {
     pub project: Account<'info, TokenProject>,
     
     #[account(mut)]
-    pub mint: Account<'info, Mint>,
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
     
     #[account(mut)]
-    pub seller: Signer<'info>,
+    pub authority: Signer<'info>,
+    
+    #[account(mut)]
+    pub liquidity_pool: SystemAccount<'info>,
+    
+    /// CHECK: The project's WSOL associated token account, checked when wrapping.
+    /// Required for SOL-priced projects only, like the other WSOL accounts.
+    #[account(mut)]
+    pub project_wsol_account: Option<UncheckedAccount<'info>>,
     
     #[account(
         mut,
-        associated_token::mint = mint,
-        associated_token::authority = seller,
+        token::mint = wsol_mint,
+        token::token_program = token_program,
     )]
-    pub seller_token_account: Account<'info, TokenAccount>,
+    pub pool_wsol_account: Option<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    
+    /// CHECK: Checked against the native mint address
+    #[account(address = anchor_spl::token::spl_token::native_mint::ID)]
+    pub wsol_mint: Option<UncheckedAccount<'info>>,
+    
+    /// Quote mint, required when the project is priced in an SPL token
+    pub quote_mint: Option<InterfaceAccount<'info, token_interface::Mint>>,
     
     #[account(
         mut,
-        seeds = [b"fee_vault"],
+        seeds = [b"quote_vault", mint.key().as_ref()],
         bump
     )]
-    pub fee_vault: SystemAccount<'info>,
+    pub quote_vault: Option<InterfaceAccount<'info, token_interface::TokenAccount>>,
     
     #[account(
-        init_if_needed,
-        payer = seller,
-        space = 8 + std::mem::size_of::<BundleTracker>(),
-        seeds = [b"bundle", mint.key().as_ref(), seller.key().as_ref()],
-        bump
+        mut,
+        token::mint = quote_mint,
+        token::token_program = quote_token_program,
     )]
-    pub bundle_tracker: Option<Account<'info, BundleTracker>>,
+    pub pool_quote_account: Option<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    
+    pub quote_token_program: Option<Interface<'info, token_interface::TokenInterface>>,
     
     pub system_program: Program<'info, System>,
+    /// WSOL lives under the legacy token program, unlike the project's Token-2022 mint
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
 }
 
 #[derive(Accounts)]
-pub struct RegisterWalletRelationship<'info> {
-    pub config: Account<'info, LaunchpadConfig>,
-    
+pub struct SetReferralFee<'info> {
     #[account(
-        init,
-        payer = authority,
-        space = 8 + std::mem::size_of::<WalletRelationship>(),
-        seeds = [b"relationship", mint.key().as_ref(), wallet_a.key().as_ref(), wallet_b.key().as_ref()],
+        mut,
+        seeds = [b"config"],
         bump
     )]
-    pub relationship: Account<'info, WalletRelationship>,
-    
-    /// CHECK: This is just a pubkey parameter
-    pub mint: UncheckedAccount<'info>,
-    
-    /// CHECK: This is just a pubkey parameter
-    pub wallet_a: UncheckedAccount<'info>,
-    
-    /// CHECK: This is just a pubkey parameter
-    pub wallet_b: UncheckedAccount<'info>,
+    pub config: Account<'info, LaunchpadConfig>,
     
-    #[account(mut)]
     pub authority: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct UpdateBundleStatus<'info> {
+pub struct SetQuoteGraduationCap<'info> {
     pub config: Account<'info, LaunchpadConfig>,
     
-    #[account(
-        seeds = [b"project", mint.key().as_ref()],
-        bump
-    )]
-    pub project: Account<'info, TokenProject>,
-    
     #[account(
         init_if_needed,
         payer = authority,
-        space = 8 + std::mem::size_of::<BundleTracker>(),
-        seeds = [b"bundle", mint.key().as_ref(), wallet.key().as_ref()],
+        space = 8 + QuoteConfig::INIT_SPACE,
+        seeds = [b"quote_config", quote_mint.key().as_ref()],
         bump
     )]
-    pub bundle_tracker: Account<'info, BundleTracker>,
-    
-    /// CHECK: This is just a pubkey parameter
-    pub mint: UncheckedAccount<'info>,
+    pub quote_config: Account<'info, QuoteConfig>,
     
-    /// CHECK: This is just a pubkey parameter
-    pub wallet: UncheckedAccount<'info>,
+    pub quote_mint: InterfaceAccount<'info, token_interface::Mint>,
     
     #[account(mut)]
     pub authority: Signer<'info>,
@@ -691,51 +4305,45 @@ pub struct UpdateBundleStatus<'info> {
 }
 
 #[derive(Accounts)]
-pub struct GraduateToken<'info> {
-    pub config: Account<'info, LaunchpadConfig>,
-    
+pub struct SetFeeTiers<'info> {
     #[account(
         mut,
-        seeds = [b"project", mint.key().as_ref()],
+        seeds = [b"config"],
         bump
     )]
-    pub project: Account<'info, TokenProject>,
-    
-    /// CHECK: This is just a pubkey parameter
-    pub mint: UncheckedAccount<'info>,
+    pub config: Account<'info, LaunchpadConfig>,
     
-    /// CHECK: This is just a pubkey parameter
-    pub liquidity_pool: UncheckedAccount<'info>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterReferrer<'info> {
+    #[account(
+        init,
+        payer = wallet,
+        space = 8 + Referrer::INIT_SPACE,
+        seeds = [b"referrer", wallet.key().as_ref()],
+        bump
+    )]
+    pub referrer: Account<'info, Referrer>,
     
     #[account(mut)]
-    pub authority: Signer<'info>,
+    pub wallet: Signer<'info>,
     
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct CreateRaydiumPool<'info> {
-    pub config: Account<'info, LaunchpadConfig>,
-    
+pub struct ClaimReferralFees<'info> {
     #[account(
         mut,
-        seeds = [b"project", mint.key().as_ref()],
+        seeds = [b"referrer", wallet.key().as_ref()],
         bump
     )]
-    pub project: Account<'info, TokenProject>,
-    
-    #[account(mut)]
-    pub mint: Account<'info, Mint>,
+    pub referrer: Account<'info, Referrer>,
     
     #[account(mut)]
-    pub authority: Signer<'info>,
-    
-    #[account(mut)]
-    pub liquidity_pool: SystemAccount<'info>,
-    
-    pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
-    pub rent: Sysvar<'info, Rent>,
+    pub wallet: Signer<'info>,
 }
 
 #[derive(Accounts)]
@@ -759,6 +4367,7 @@ pub struct WithdrawPlatformFees<'info> {
 }
 
 #[account]
+#[derive(InitSpace)]
 pub struct LaunchpadConfig {
     pub authority: Pubkey,
     pub fee_recipient: Pubkey,
@@ -766,36 +4375,153 @@ pub struct LaunchpadConfig {
     pub graduation_market_cap: u64,
     pub trading_fee_bps: u16, // 100 = 1%
     pub relationship_threshold: u16, // 300 = 3%
+    pub version: u8, // Layout version, later fields are appended in version order, see migration.rs
+    pub bundle_penalty_mode: BundlePenaltyMode,
+    pub funding_window_slots: u64, // Max slot gap for a common funding source
+    pub bundle_flag_duration: i64, // Seconds a bundling flag lasts, 0 = until cleared
+    pub snipe_window_slots: u64, // Slots after launch that count as sniping, 0 = disabled
+    pub snipe_tax_bps: u16, // Extra tax on buys inside the sniping window
+    pub snipe_block: bool, // Flag the sniper cluster as bundling outright
+    pub referral_fee_bps: u16, // Referrer's share of the trading fee, carved from the platform half
+    #[max_len(MAX_FEE_TIERS)]
+    pub fee_tiers: Vec<FeeTier>, // Reduced fees by rolling 30-day volume, ascending
 }
 
+/// What happens to tokens held by a wallet flagged as bundling
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum BundlePenaltyMode {
+    /// Reject transfers from the wallet
+    Block,
+    /// Sweep the wallet's tokens into the mint's penalty vault
+    Confiscate,
+    /// Burn the wallet's tokens
+    Burn,
+}
+
+/// Maximum length of a project name in bytes
+pub const MAX_NAME_LEN: usize = 32;
+
+/// Maximum length of a project symbol in bytes
+pub const MAX_SYMBOL_LEN: usize = 10;
+
+/// Maximum number of bonding curve parameters
+pub const MAX_CURVE_PARAMS: usize = 4;
+
 #[account]
+#[derive(InitSpace)]
 pub struct TokenProject {
     pub mint: Pubkey,
     pub creator: Pubkey,
+    #[max_len(MAX_NAME_LEN)]
     pub name: String,
+    #[max_len(MAX_SYMBOL_LEN)]
     pub symbol: String,
     pub initial_price: u64,
     pub current_price: u64,
     pub supply: u64,
     pub reserve_balance: u64,
+    #[max_len(MAX_CURVE_PARAMS)]
     pub curve_params: Vec<u64>,
     pub is_graduated: bool,
     pub liquidity_pool: Option<Pubkey>,
     pub creator_fee_earned: u64,
     pub platform_fee_earned: u64,
+    pub version: u8, // Layout version, later fields are appended in version order, see migration.rs
+    pub launch_slot: u64,
+    pub max_wallet_bps: Option<u16>, // Max share of supply per wallet until graduation
+    pub max_buy_per_tx: Option<u64>, // Max tokens per buy until graduation
+    pub buy_cooldown_secs: Option<u32>, // Min seconds between buys from a wallet or cluster
+    pub quote_mint: Option<Pubkey>, // SPL token the project is priced in, None = native SOL
+    pub quote_decimals: u8, // Decimals of the quote token, 9 for SOL
+    pub creator_allocation: u64, // Creator's dev-buy still held, excluded from bundle checks
+    pub premint_supply: u64, // Tokens minted outside the curve, e.g. a vesting allocation
+    pub launch_at: i64, // Unix time buys open
+    pub public_open_at: i64, // Unix time buys open to everyone, equal to launch_at without a whitelist
+    pub whitelist_root: Option<[u8; 32]>, // Merkle root of whitelisted wallets, see launch_schedule.rs
+    pub whitelist_cap: u64, // Max tokens per wallet during the whitelist phase
+    pub launch_fee: Option<LaunchFeeCurve>, // Decaying buy fee right after launch
+    pub min_raise: Option<u64>, // Reserve a refundable launch must reach by the deadline
+    pub deadline: i64, // Unix time the minimum raise is checked
+    pub min_raise_met: bool,
+    pub has_third_party_buys: bool, // Set by the first buy from anyone but the creator
+    pub is_cancelled: bool, // Trading has stopped and holders can claim refunds
+    pub graduation_market_cap: u64, // In base units of the quote token, fixed at launch
+}
+
+/// An extra key/value pair in a mint's TokenMetadata, e.g. website or socials
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct MetadataField {
+    pub key: String,
+    pub value: String,
+}
+
+/// Per-slot buys for the first slots after a project launches
+#[account]
+#[derive(InitSpace)]
+pub struct LaunchLedger {
+    pub mint: Pubkey,
+    pub launch_slot: u64,
+    pub buy_counts: [u32; LAUNCH_LEDGER_SLOTS],
+    pub buy_volumes: [u64; LAUNCH_LEDGER_SLOTS],
 }
 
 #[account]
+#[derive(InitSpace)]
 pub struct BundleTracker {
     pub mint: Pubkey,
     pub wallet: Pubkey,
+    #[max_len(MAX_RELATED_WALLETS)]
     pub related_wallets: Vec<Pubkey>,
     pub total_bundle_balance: u64,
     pub is_bundling: bool,
     pub last_updated: i64,
+    pub last_buy_slot: u64,
+    pub fee_payer: Pubkey,
+    pub funding_source: Option<Pubkey>,
+    pub funding_slot: u64,
+    pub flag_reason: BundleFlagReason,
+    pub flag_expires_at: i64, // 0 = no expiry
+    pub last_buy_at: i64, // Buy cooldowns run from here, 0 = never bought
+    pub rent_payer: Pubkey, // Refunded when the tracker is closed
+}
+
+/// Why a wallet was flagged as bundling
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum BundleFlagReason {
+    /// Not flagged
+    None,
+    /// The wallet's bundle crossed the threshold on a trade or re-evaluation
+    Threshold,
+    /// The authority supplied the bundle balance
+    Manual,
+}
+
+/// A group of wallets judged together for bundling, keyed by mint and cluster id
+#[account]
+#[derive(InitSpace)]
+pub struct BundleCluster {
+    pub mint: Pubkey,
+    pub cluster_id: u64,
+    pub member_count: u32,
+    pub total_balance: u64,
+    pub force_bundling: bool, // Flagged regardless of balance, e.g. blocked snipers
+    pub is_bundling: bool,
+    pub last_updated: i64,
+    pub last_buy_at: i64, // Latest buy by any member, 0 = none yet
+}
+
+/// Points a wallet at the bundle cluster it belongs to
+#[account]
+#[derive(InitSpace)]
+pub struct ClusterMembership {
+    pub mint: Pubkey,
+    pub wallet: Pubkey,
+    pub cluster: Pubkey,
+    pub balance: u64, // The wallet's contribution to the cluster's total_balance
 }
 
 #[account]
+#[derive(InitSpace)]
 pub struct WalletRelationship {
     pub mint: Pubkey,
     pub wallet_a: Pubkey,
@@ -803,6 +4529,280 @@ pub struct WalletRelationship {
     pub relationship_strength: u16,
     pub last_transaction: i64,
     pub transaction_count: u16,
+    pub last_evidence_slot: u64, // Latest tracker signal already scored by inference
+    pub rent_payer: Pubkey, // Refunded when the relationship is closed
+}
+
+/// A promoter paid a share of the fees on the trades it refers
+#[account]
+#[derive(InitSpace)]
+pub struct Referrer {
+    pub wallet: Pubkey,
+    pub total_volume: u64, // Referred trade volume in lamports
+    pub total_fees_earned: u64,
+    pub fees_claimable: u64, // Held as lamports on this account until claimed
+    pub last_updated: i64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct TraderStats {
+    pub wallet: Pubkey,
+    pub total_volume: u64, // Lifetime SOL volume in lamports
+    pub daily_volumes: [u64; VOLUME_WINDOW_DAYS], // Ring buffer of daily SOL volume
+    pub last_day: i64, // Unix day of the most recent trade
+}
+
+/// A volume threshold and the fee charged to traders who reach it
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, InitSpace)]
+pub struct FeeTier {
+    pub min_volume: u64, // Rolling 30-day SOL volume in lamports
+    pub fee_bps: u16,
+}
+
+/// Allowlist entry for a program vault, pool, exchange or escrow address
+#[account]
+#[derive(InitSpace)]
+pub struct ExemptAddress {
+    pub address: Pubkey,
+    pub scopes: u8, // Bitmask of EXEMPT_FEES, EXEMPT_BUNDLING and EXEMPT_LIMITS
+}
+
+/// Buy fee that starts high at the launch slot and decays linearly to the normal fee
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, InitSpace)]
+pub struct LaunchFeeCurve {
+    pub start_fee_bps: u16, // Fee in the launch slot, e.g. 5000 = 50%
+    pub decay_slots: u64, // Slots until the fee is back to normal
+}
+
+/// Minimum raise a refundable launch must reach by its deadline
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct MinRaise {
+    pub amount: u64, // Lamports the reserve must reach
+    pub deadline: i64,
+}
+
+/// Whitelist phase between `launch_at` and public trading
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct WhitelistPhase {
+    pub merkle_root: [u8; 32],
+    pub wallet_cap: u64, // Max tokens per wallet during the phase
+    pub public_open_at: i64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct WhitelistPurchase {
+    pub mint: Pubkey,
+    pub wallet: Pubkey,
+    pub purchased: u64, // Tokens bought during the whitelist phase
+}
+
+/// Creator allocation locked in a `VestingEscrow` at launch
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct VestingSchedule {
+    pub amount: u64,
+    pub cliff_secs: i64, // Nothing unlocks before the cliff
+    pub duration_secs: i64, // Everything has unlocked by the end
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct VestingEscrow {
+    pub mint: Pubkey,
+    pub beneficiary: Pubkey,
+    pub total_amount: u64,
+    pub claimed_amount: u64,
+    pub start_time: i64,
+    pub cliff_time: i64,
+    pub end_time: i64,
+}
+
+/// Limit order against the bonding curve, executed by a keeper once the price crosses its trigger
+#[account]
+#[derive(InitSpace)]
+pub struct Order {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub order_id: u64, // Chosen by the owner to tell their orders apart
+    pub side: OrderSide,
+    pub trigger_price: u64, // Buys fill at or below this price, sells at or above
+    pub amount: u64, // Tokens to buy or sell
+    pub escrowed: u64, // Max lamports a buy may cost, or tokens a sell burned when placed
+    pub min_proceeds: u64, // Least lamports a sell may pay out after fees
+    pub keeper_fee: u64, // Lamports paid to whoever executes the order
+    pub expires_at: i64,
+    pub created_at: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum OrderSide {
+    Buy,
+    Sell,
+}
+
+/// Batch auction that opens an auctioned project
+#[account]
+#[derive(InitSpace)]
+pub struct LaunchAuction {
+    pub mint: Pubkey,
+    pub ends_at: i64,
+    pub total_committed: u64, // Lamports escrowed on this account
+    pub commitment_count: u32, // Commitments not yet distributed
+    pub settled: bool,
+    pub clearing_tokens: u64, // Tokens bought for all commitments at settlement
+    pub refund_pool: u64, // Committed SOL the curve did not use
+    pub distributed_tokens: u64,
+    pub distributed_refund: u64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct AuctionCommitment {
+    pub mint: Pubkey,
+    pub wallet: Pubkey,
+    pub amount: u64,
+}
+
+/// Graduation market cap for projects priced in a quote token, set by the authority
+#[account]
+#[derive(InitSpace)]
+pub struct QuoteConfig {
+    pub quote_mint: Pubkey,
+    pub graduation_market_cap: u64, // In base units of the quote token
+}
+
+#[event]
+pub struct LaunchCommitted {
+    pub mint: Pubkey,
+    pub wallet: Pubkey,
+    pub amount: u64,
+    pub wallet_committed: u64,
+    pub total_committed: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LaunchAuctionSettled {
+    pub mint: Pubkey,
+    pub total_committed: u64,
+    pub commitment_count: u32,
+    pub tokens: u64,
+    pub curve_cost: u64,
+    pub refund_pool: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuctionAllocationDistributed {
+    pub mint: Pubkey,
+    pub wallet: Pubkey,
+    pub committed: u64,
+    pub tokens: u64,
+    pub refund: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OrderPlaced {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub order: Pubkey,
+    pub side: OrderSide,
+    pub trigger_price: u64,
+    pub amount: u64,
+    pub escrowed: u64,
+    pub min_proceeds: u64,
+    pub keeper_fee: u64,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OrderCancelled {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub order: Pubkey,
+    pub cancelled_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OrderExecuted {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub order: Pubkey,
+    pub side: OrderSide,
+    pub amount: u64,
+    pub price: u64, // Cost of a buy or proceeds of a sell, before fees
+    pub keeper: Pubkey,
+    pub keeper_fee: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CommitmentWithdrawn {
+    pub mint: Pubkey,
+    pub wallet: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LaunchCancelled {
+    pub mint: Pubkey,
+    pub cancelled_by: Pubkey,
+    pub by_creator: bool,
+    pub reserve_balance: u64,
+    pub supply: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RefundClaimed {
+    pub mint: Pubkey,
+    pub holder: Pubkey,
+    pub amount: u64,
+    pub refund: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VestedTokensClaimed {
+    pub mint: Pubkey,
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+    pub claimed_amount: u64,
+    pub total_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CreatorAllocationRecorded {
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub sol_cost: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BundledTokensSeized {
+    pub mint: Pubkey,
+    pub wallet: Pubkey,
+    pub amount: u64,
+    pub mode: BundlePenaltyMode,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BundleFlagCleared {
+    pub mint: Pubkey,
+    pub wallet: Pubkey,
+    pub previous_reason: BundleFlagReason,
+    pub cleared_by: Pubkey,
+    pub by_authority: bool,
+    pub timestamp: i64,
 }
 
 #[error_code]
@@ -827,4 +4827,387 @@ pub enum ErrorCode {
     UnsupportedInstruction,
     #[msg("Incorrect transfer hook program")]
     IncorrectTransferHookProgram,
-}
\ No newline at end of file
+    #[msg("Bundle penalty mode does not allow seizing tokens")]
+    BundlePenaltyDisabled,
+    #[msg("Wallet is not bundling")]
+    WalletNotBundling,
+    #[msg("Nothing to seize")]
+    NothingToSeize,
+    #[msg("Invalid relationship accounts")]
+    InvalidRelationshipAccounts,
+    #[msg("Too many related wallets")]
+    TooManyRelatedWallets,
+    #[msg("Bundle cluster does not match membership")]
+    ClusterMismatch,
+    #[msg("No evidence that the wallets are related")]
+    NoRelationshipEvidence,
+    #[msg("Invalid sniping rules")]
+    InvalidSnipeRules,
+    #[msg("Buys in the sniping window must join the sniper cluster")]
+    SniperClusterRequired,
+    #[msg("Invalid trading limits")]
+    InvalidTradingLimits,
+    #[msg("Buy exceeds the per-transaction limit")]
+    MaxBuyExceeded,
+    #[msg("Wallet balance exceeds the max-wallet limit")]
+    MaxWalletExceeded,
+    #[msg("Bundle tracker is required")]
+    BundleTrackerRequired,
+    #[msg("Buy cooldown is still active")]
+    BuyCooldownActive,
+    #[msg("Invalid bundle flag duration")]
+    InvalidBundleFlagDuration,
+    #[msg("Invalid exemption scopes")]
+    InvalidExemptionScopes,
+    #[msg("Bundle tracker still holds a balance or a live flag")]
+    BundleTrackerInUse,
+    #[msg("Project has not graduated")]
+    ProjectNotGraduated,
+    #[msg("Invalid accounts to close")]
+    InvalidCloseAccounts,
+    #[msg("Project name is too long")]
+    NameTooLong,
+    #[msg("Project symbol is too long")]
+    SymbolTooLong,
+    #[msg("Too many curve parameters")]
+    TooManyCurveParams,
+    #[msg("Unsupported account version")]
+    UnsupportedAccountVersion,
+    #[msg("Account is already on the current version")]
+    AccountAlreadyMigrated,
+    #[msg("Metadata URI is too long")]
+    UriTooLong,
+    #[msg("Too many or too long metadata fields")]
+    TooManyMetadataFields,
+    #[msg("Metadata is locked after graduation")]
+    MetadataLocked,
+    #[msg("Invalid mint decimals")]
+    InvalidDecimals,
+    #[msg("Quote token accounts are required for this project")]
+    QuoteAccountsRequired,
+    #[msg("Quote token account does not match the project")]
+    QuoteAccountMismatch,
+    #[msg("Invalid WSOL account")]
+    InvalidWsolAccount,
+    #[msg("Invalid referral fee")]
+    InvalidReferralFee,
+    #[msg("Traders cannot refer themselves")]
+    SelfReferral,
+    #[msg("Nothing to claim")]
+    NothingToClaim,
+    #[msg("Fee tiers must ascend in volume, descend in fee and not exceed the trading fee")]
+    InvalidFeeTiers,
+    #[msg("Dev-buys are only supported for SOL-priced launches")]
+    UnsupportedDevBuyQuote,
+    #[msg("Fee vault, creator token account and associated token program are required for a dev-buy")]
+    DevBuyAccountsRequired,
+    #[msg("Trade cost exceeds the caller's limit")]
+    SlippageExceeded,
+    #[msg("Invalid vesting schedule")]
+    InvalidVestingSchedule,
+    #[msg("Vesting escrow, vault and associated token program are required for a vesting allocation")]
+    VestingAccountsRequired,
+    #[msg("Invalid launch schedule")]
+    InvalidLaunchSchedule,
+    #[msg("Trading has not opened yet")]
+    LaunchNotStarted,
+    #[msg("Wallet is not on the whitelist")]
+    NotWhitelisted,
+    #[msg("Whitelist purchase account is required during the whitelist phase")]
+    WhitelistPurchaseRequired,
+    #[msg("Buy exceeds the whitelist allocation")]
+    WhitelistCapExceeded,
+    #[msg("Invalid launch fee curve")]
+    InvalidLaunchFee,
+    #[msg("Launch auctions are only supported for SOL-quoted projects")]
+    UnsupportedAuctionQuote,
+    #[msg("Launch auction account must be passed exactly when auction_ends_at is set")]
+    LaunchAuctionRequired,
+    #[msg("Launch auction is closed to commitments")]
+    AuctionClosed,
+    #[msg("Launch auction has not ended")]
+    AuctionNotEnded,
+    #[msg("Commitment must be greater than zero")]
+    InvalidCommitment,
+    #[msg("Invalid auction distribution accounts")]
+    InvalidAuctionAccounts,
+    #[msg("Invalid minimum raise")]
+    InvalidMinRaise,
+    #[msg("Refundable launches are only supported for SOL-quoted projects")]
+    UnsupportedRefundQuote,
+    #[msg("Project has been cancelled")]
+    ProjectCancelled,
+    #[msg("Project has not been cancelled")]
+    ProjectNotCancelled,
+    #[msg("Launch cannot be cancelled")]
+    CancelNotAllowed,
+    #[msg("Invalid order")]
+    InvalidOrder,
+    #[msg("Limit orders are only supported for SOL-quoted projects")]
+    UnsupportedOrderQuote,
+    #[msg("Owner token account is required for a sell order")]
+    OrderAccountsRequired,
+    #[msg("Order cannot be executed yet")]
+    OrderNotTriggered,
+    #[msg("Order has expired")]
+    OrderExpired,
+    #[msg("Launch auction was not settled in time, commitments can be withdrawn")]
+    AuctionSettlementExpired,
+    #[msg("Commitments can only be withdrawn from an auction that missed its settlement window or whose launch was cancelled")]
+    CommitmentLocked,
+    #[msg("Vesting allocation exceeds the share of the graduation supply a creator can premint")]
+    VestingAllocationTooLarge,
+    #[msg("Quote mint has no graduation market cap configured")]
+    QuoteNotConfigured,
+    #[msg("Invalid graduation market cap")]
+    InvalidGraduationCap,
+    #[msg("Referrals are only supported for SOL-priced launches")]
+    UnsupportedReferralQuote,
+    #[msg("Fee tiers are only supported for SOL-priced launches")]
+    UnsupportedFeeTierQuote,
+    #[msg("Dev-buys are not supported for auction launches")]
+    UnsupportedDevBuyAuction,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A live SOL-quoted project on a flat curve at 1000 lamports per token, for the
+    /// module tests
+    pub(crate) fn project_fixture() -> TokenProject {
+        TokenProject {
+            mint: Pubkey::new_unique(),
+            creator: Pubkey::new_unique(),
+            name: "Organic".to_string(),
+            symbol: "ORG".to_string(),
+            initial_price: 1000,
+            current_price: 1000,
+            supply: 0,
+            reserve_balance: 0,
+            curve_params: vec![10000, 1000],
+            is_graduated: false,
+            liquidity_pool: None,
+            creator_fee_earned: 0,
+            platform_fee_earned: 0,
+            version: PROJECT_VERSION,
+            launch_slot: 0,
+            max_wallet_bps: None,
+            max_buy_per_tx: None,
+            buy_cooldown_secs: None,
+            quote_mint: None,
+            quote_decimals: SOL_DECIMALS,
+            creator_allocation: 0,
+            premint_supply: 0,
+            launch_at: 0,
+            public_open_at: 0,
+            whitelist_root: None,
+            whitelist_cap: 0,
+            launch_fee: None,
+            min_raise: None,
+            deadline: 0,
+            min_raise_met: false,
+            has_third_party_buys: false,
+            is_cancelled: false,
+            graduation_market_cap: 1_000_000_000,
+        }
+    }
+
+    /// Serialize an account at capacity and check it fills its allocation exactly
+    fn assert_fits<T: AnchorSerialize>(account: &T, init_space: usize) {
+        let data = account.try_to_vec().unwrap();
+        assert_eq!(data.len(), init_space);
+    }
+
+    #[test]
+    fn token_project_fits_at_capacity() {
+        let project = TokenProject {
+            mint: Pubkey::new_unique(),
+            creator: Pubkey::new_unique(),
+            name: "N".repeat(MAX_NAME_LEN),
+            symbol: "S".repeat(MAX_SYMBOL_LEN),
+            initial_price: u64::MAX,
+            current_price: u64::MAX,
+            supply: u64::MAX,
+            reserve_balance: u64::MAX,
+            curve_params: vec![u64::MAX; MAX_CURVE_PARAMS],
+            is_graduated: true,
+            liquidity_pool: Some(Pubkey::new_unique()),
+            creator_fee_earned: u64::MAX,
+            platform_fee_earned: u64::MAX,
+            version: PROJECT_VERSION,
+            launch_slot: u64::MAX,
+            max_wallet_bps: Some(u16::MAX),
+            max_buy_per_tx: Some(u64::MAX),
+            buy_cooldown_secs: Some(u32::MAX),
+            quote_mint: Some(Pubkey::new_unique()),
+            quote_decimals: u8::MAX,
+            creator_allocation: u64::MAX,
+            premint_supply: u64::MAX,
+            launch_at: i64::MAX,
+            public_open_at: i64::MAX,
+            whitelist_root: Some([u8::MAX; 32]),
+            whitelist_cap: u64::MAX,
+            launch_fee: Some(LaunchFeeCurve { start_fee_bps: u16::MAX, decay_slots: u64::MAX }),
+            min_raise: Some(u64::MAX),
+            deadline: i64::MAX,
+            min_raise_met: true,
+            has_third_party_buys: true,
+            is_cancelled: true,
+            graduation_market_cap: u64::MAX,
+        };
+        
+        assert_fits(&project, TokenProject::INIT_SPACE);
+    }
+
+    #[test]
+    fn bundle_tracker_fits_at_capacity() {
+        let bundle_tracker = BundleTracker {
+            mint: Pubkey::new_unique(),
+            wallet: Pubkey::new_unique(),
+            related_wallets: (0..MAX_RELATED_WALLETS).map(|_| Pubkey::new_unique()).collect(),
+            total_bundle_balance: u64::MAX,
+            is_bundling: true,
+            last_updated: i64::MAX,
+            last_buy_slot: u64::MAX,
+            fee_payer: Pubkey::new_unique(),
+            funding_source: Some(Pubkey::new_unique()),
+            funding_slot: u64::MAX,
+            flag_reason: BundleFlagReason::Manual,
+            flag_expires_at: i64::MAX,
+            last_buy_at: i64::MAX,
+            rent_payer: Pubkey::new_unique(),
+        };
+        
+        assert_fits(&bundle_tracker, BundleTracker::INIT_SPACE);
+    }
+
+    #[test]
+    fn fixed_size_accounts_fit() {
+        let config = LaunchpadConfig {
+            authority: Pubkey::new_unique(),
+            fee_recipient: Pubkey::new_unique(),
+            bundle_threshold_percentage: u16::MAX,
+            graduation_market_cap: u64::MAX,
+            trading_fee_bps: u16::MAX,
+            relationship_threshold: u16::MAX,
+            version: CONFIG_VERSION,
+            bundle_penalty_mode: BundlePenaltyMode::Burn,
+            funding_window_slots: u64::MAX,
+            bundle_flag_duration: i64::MAX,
+            snipe_window_slots: u64::MAX,
+            snipe_tax_bps: u16::MAX,
+            snipe_block: true,
+            referral_fee_bps: u16::MAX,
+            fee_tiers: vec![FeeTier { min_volume: u64::MAX, fee_bps: u16::MAX }; MAX_FEE_TIERS],
+        };
+        assert_fits(&config, LaunchpadConfig::INIT_SPACE);
+        
+        let launch_ledger = LaunchLedger {
+            mint: Pubkey::new_unique(),
+            launch_slot: u64::MAX,
+            buy_counts: [u32::MAX; LAUNCH_LEDGER_SLOTS],
+            buy_volumes: [u64::MAX; LAUNCH_LEDGER_SLOTS],
+        };
+        assert_fits(&launch_ledger, LaunchLedger::INIT_SPACE);
+        
+        let cluster = BundleCluster {
+            mint: Pubkey::new_unique(),
+            cluster_id: u64::MAX,
+            member_count: u32::MAX,
+            total_balance: u64::MAX,
+            force_bundling: true,
+            is_bundling: true,
+            last_updated: i64::MAX,
+            last_buy_at: i64::MAX,
+        };
+        assert_fits(&cluster, BundleCluster::INIT_SPACE);
+        
+        let membership = ClusterMembership {
+            mint: Pubkey::new_unique(),
+            wallet: Pubkey::new_unique(),
+            cluster: Pubkey::new_unique(),
+            balance: u64::MAX,
+        };
+        assert_fits(&membership, ClusterMembership::INIT_SPACE);
+        
+        let relationship = WalletRelationship {
+            mint: Pubkey::new_unique(),
+            wallet_a: Pubkey::new_unique(),
+            wallet_b: Pubkey::new_unique(),
+            relationship_strength: u16::MAX,
+            last_transaction: i64::MAX,
+            transaction_count: u16::MAX,
+            last_evidence_slot: u64::MAX,
+            rent_payer: Pubkey::new_unique(),
+        };
+        assert_fits(&relationship, WalletRelationship::INIT_SPACE);
+        
+        let exempt_address = ExemptAddress {
+            address: Pubkey::new_unique(),
+            scopes: EXEMPT_ALL,
+        };
+        assert_fits(&exempt_address, ExemptAddress::INIT_SPACE);
+        
+        let trader_stats = TraderStats {
+            wallet: Pubkey::new_unique(),
+            total_volume: u64::MAX,
+            daily_volumes: [u64::MAX; VOLUME_WINDOW_DAYS],
+            last_day: i64::MAX,
+        };
+        assert_fits(&trader_stats, TraderStats::INIT_SPACE);
+        
+        let vesting_escrow = VestingEscrow {
+            mint: Pubkey::new_unique(),
+            beneficiary: Pubkey::new_unique(),
+            total_amount: u64::MAX,
+            claimed_amount: u64::MAX,
+            start_time: i64::MAX,
+            cliff_time: i64::MAX,
+            end_time: i64::MAX,
+        };
+        assert_fits(&vesting_escrow, VestingEscrow::INIT_SPACE);
+        
+        let whitelist_purchase = WhitelistPurchase {
+            mint: Pubkey::new_unique(),
+            wallet: Pubkey::new_unique(),
+            purchased: u64::MAX,
+        };
+        assert_fits(&whitelist_purchase, WhitelistPurchase::INIT_SPACE);
+        
+        let launch_auction = LaunchAuction {
+            mint: Pubkey::new_unique(),
+            ends_at: i64::MAX,
+            total_committed: u64::MAX,
+            commitment_count: u32::MAX,
+            settled: true,
+            clearing_tokens: u64::MAX,
+            refund_pool: u64::MAX,
+            distributed_tokens: u64::MAX,
+            distributed_refund: u64::MAX,
+        };
+        assert_fits(&launch_auction, LaunchAuction::INIT_SPACE);
+        
+        let commitment = AuctionCommitment {
+            mint: Pubkey::new_unique(),
+            wallet: Pubkey::new_unique(),
+            amount: u64::MAX,
+        };
+        assert_fits(&commitment, AuctionCommitment::INIT_SPACE);
+        
+        let order = Order {
+            mint: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            order_id: u64::MAX,
+            side: OrderSide::Sell,
+            trigger_price: u64::MAX,
+            amount: u64::MAX,
+            escrowed: u64::MAX,
+            min_proceeds: u64::MAX,
+            keeper_fee: u64::MAX,
+            expires_at: i64::MAX,
+            created_at: i64::MAX,
+        };
+        assert_fits(&order, Order::INIT_SPACE);
+    }
+}
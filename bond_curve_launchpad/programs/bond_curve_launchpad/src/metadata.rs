@@ -0,0 +1,208 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use anchor_lang::system_program;
+use spl_token_2022::{
+    extension::{metadata_pointer, BaseStateWithExtensions, StateWithExtensions},
+    state::Mint,
+};
+use spl_token_metadata_interface::state::{Field, TokenMetadata};
+
+use crate::MetadataField;
+
+/// Maximum length of a metadata URI in bytes
+pub const MAX_URI_LEN: usize = 200;
+
+/// Maximum number of extra metadata fields, e.g. website and socials
+pub const MAX_EXTRA_METADATA_FIELDS: usize = 8;
+
+/// Maximum length of an extra metadata key or value in bytes
+pub const MAX_METADATA_FIELD_LEN: usize = 128;
+
+/// Check a URI and extra fields against the metadata caps
+pub fn validate_metadata(uri: &str, extra_metadata: &[MetadataField]) -> Result<()> {
+    if uri.len() > MAX_URI_LEN {
+        return Err(error!(ErrorCode::UriTooLong));
+    }
+    
+    if extra_metadata.len() > MAX_EXTRA_METADATA_FIELDS {
+        return Err(error!(ErrorCode::TooManyMetadataFields));
+    }
+    
+    for field in extra_metadata {
+        if field.key.len() > MAX_METADATA_FIELD_LEN || field.value.len() > MAX_METADATA_FIELD_LEN {
+            return Err(error!(ErrorCode::TooManyMetadataFields));
+        }
+    }
+    
+    Ok(())
+}
+
+/// Point the mint's metadata at the mint itself. Must run before the mint is initialized.
+pub fn initialize_metadata_pointer(
+    mint: &AccountInfo,
+    update_authority: &Pubkey,
+    token_program: &AccountInfo,
+) -> Result<()> {
+    let ix = metadata_pointer::instruction::initialize(
+        token_program.key,
+        mint.key,
+        Some(*update_authority),
+        Some(*mint.key),
+    )?;
+    
+    invoke(
+        &ix,
+        &[
+            mint.clone(),
+            token_program.clone(),
+        ],
+    )?;
+    
+    Ok(())
+}
+
+/// Write name, symbol, URI and extra fields into the mint's TokenMetadata extension
+pub fn initialize_token_metadata<'info>(
+    mint: &AccountInfo<'info>,
+    update_authority: &AccountInfo<'info>,
+    mint_authority: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    system_program_info: &AccountInfo<'info>,
+    name: String,
+    symbol: String,
+    uri: String,
+    extra_metadata: &[MetadataField],
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    // Fund the mint for the variable-length extension before Token-2022 grows it
+    let metadata = TokenMetadata {
+        name: name.clone(),
+        symbol: symbol.clone(),
+        uri: uri.clone(),
+        additional_metadata: extra_metadata
+            .iter()
+            .map(|field| (field.key.clone(), field.value.clone()))
+            .collect(),
+        ..Default::default()
+    };
+    fund_mint_space(mint, payer, system_program_info, metadata.tlv_size_of()?)?;
+    
+    let ix = spl_token_metadata_interface::instruction::initialize(
+        token_program.key,
+        mint.key,
+        update_authority.key,
+        mint.key,
+        mint_authority.key,
+        name,
+        symbol,
+        uri,
+    );
+    
+    invoke_signed(
+        &ix,
+        &[
+            mint.clone(),
+            update_authority.clone(),
+            mint_authority.clone(),
+            token_program.clone(),
+        ],
+        signer_seeds,
+    )?;
+    
+    for field in extra_metadata {
+        update_metadata_field(
+            mint,
+            update_authority,
+            token_program,
+            Field::Key(field.key.clone()),
+            field.value.clone(),
+            signer_seeds,
+        )?;
+    }
+    
+    Ok(())
+}
+
+/// Update one metadata field, topping up the mint's rent if the metadata grows
+pub fn set_metadata_field<'info>(
+    mint: &AccountInfo<'info>,
+    update_authority: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    system_program_info: &AccountInfo<'info>,
+    field: Field,
+    value: String,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let growth = {
+        let mint_data = mint.try_borrow_data()?;
+        let mint_state = StateWithExtensions::<Mint>::unpack(&mint_data)?;
+        let mut metadata = mint_state.get_variable_len_extension::<TokenMetadata>()?;
+        let current_size = metadata.tlv_size_of()?;
+        
+        metadata.update(field.clone(), value.clone());
+        metadata.tlv_size_of()?.saturating_sub(current_size)
+    };
+    
+    if growth > 0 {
+        fund_mint_space(mint, payer, system_program_info, growth)?;
+    }
+    
+    update_metadata_field(mint, update_authority, token_program, field, value, signer_seeds)
+}
+
+fn update_metadata_field<'info>(
+    mint: &AccountInfo<'info>,
+    update_authority: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    field: Field,
+    value: String,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let ix = spl_token_metadata_interface::instruction::update_field(
+        token_program.key,
+        mint.key,
+        update_authority.key,
+        field,
+        value,
+    );
+    
+    invoke_signed(
+        &ix,
+        &[
+            mint.clone(),
+            update_authority.clone(),
+            token_program.clone(),
+        ],
+        signer_seeds,
+    )?;
+    
+    Ok(())
+}
+
+/// Transfer enough lamports to keep the mint rent-exempt once it grows by `additional_space` bytes
+fn fund_mint_space<'info>(
+    mint: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program_info: &AccountInfo<'info>,
+    additional_space: usize,
+) -> Result<()> {
+    let required = Rent::get()?.minimum_balance(mint.data_len() + additional_space);
+    let lamports = mint.lamports();
+    
+    if required > lamports {
+        system_program::transfer(
+            CpiContext::new(
+                system_program_info.clone(),
+                system_program::Transfer {
+                    from: payer.clone(),
+                    to: mint.clone(),
+                },
+            ),
+            required - lamports,
+        )?;
+    }
+    
+    Ok(())
+}
@@ -0,0 +1,542 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::Discriminator;
+
+use crate::{BundlePenaltyMode, ErrorCode, LaunchpadConfig, QuoteConfig, TokenProject, MAX_CURVE_PARAMS, MAX_NAME_LEN, MAX_SYMBOL_LEN};
+use crate::bond_curve::SOL_DECIMALS;
+use crate::quote::quote_graduation_market_cap;
+
+/// Current layout version of `LaunchpadConfig`
+pub const CONFIG_VERSION: u8 = 4;
+
+/// Current layout version of `TokenProject`
+pub const PROJECT_VERSION: u8 = 9;
+
+/// Layout of `LaunchpadConfig` before the version byte was added. Every later field
+/// is appended after the version byte, in the order its version added it.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LaunchpadConfigV1 {
+    pub authority: Pubkey,
+    pub fee_recipient: Pubkey,
+    pub bundle_threshold_percentage: u16,
+    pub graduation_market_cap: u64,
+    pub trading_fee_bps: u16,
+    pub relationship_threshold: u16,
+}
+
+/// Layout of `TokenProject` before the version byte was added. Every later field is
+/// appended after the version byte, in the order its version added it.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct TokenProjectV1 {
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub initial_price: u64,
+    pub current_price: u64,
+    pub supply: u64,
+    pub reserve_balance: u64,
+    pub curve_params: Vec<u64>,
+    pub is_graduated: bool,
+    pub liquidity_pool: Option<Pubkey>,
+    pub creator_fee_earned: u64,
+    pub platform_fee_earned: u64,
+}
+
+/// Read a config account in any known layout and upgrade it to the current one.
+/// Returns `None` if the account is already current.
+pub fn upgrade_config(data: &[u8]) -> Result<Option<LaunchpadConfig>> {
+    let mut body = account_body(data, &LaunchpadConfig::DISCRIMINATOR)?;
+    let v1 = read_field::<LaunchpadConfigV1>(&mut body)?;
+    
+    let version = read_version(&mut body, CONFIG_VERSION)?;
+    if version == CONFIG_VERSION {
+        return Ok(None);
+    }
+    
+    // Fields a version did not have yet keep the behaviour the account had before it
+    Ok(Some(LaunchpadConfig {
+        authority: v1.authority,
+        fee_recipient: v1.fee_recipient,
+        bundle_threshold_percentage: v1.bundle_threshold_percentage,
+        graduation_market_cap: v1.graduation_market_cap,
+        trading_fee_bps: v1.trading_fee_bps,
+        relationship_threshold: v1.relationship_threshold,
+        version: CONFIG_VERSION,
+        // v2: bundle penalties, common funding, flag expiry and sniper rules
+        bundle_penalty_mode: added_in(&mut body, version, 2, BundlePenaltyMode::Block)?,
+        funding_window_slots: added_in(&mut body, version, 2, 150)?,
+        bundle_flag_duration: added_in(&mut body, version, 2, 0)?,
+        snipe_window_slots: added_in(&mut body, version, 2, 0)?,
+        snipe_tax_bps: added_in(&mut body, version, 2, 0)?,
+        snipe_block: added_in(&mut body, version, 2, false)?,
+        // v3: referral fees
+        referral_fee_bps: added_in(&mut body, version, 3, 0)?,
+        // v4: fee tiers
+        fee_tiers: added_in(&mut body, version, 4, Vec::new())?,
+    }))
+}
+
+/// Read a project account in any known layout and upgrade it to the current one.
+/// Returns `None` if the account is already current.
+///
+/// Projects that predate per-project graduation caps take the launchpad's
+/// `graduation_market_cap` when priced in SOL, or the cap in `quote_config` when
+/// priced in a quote token.
+pub fn upgrade_project(
+    data: &[u8],
+    graduation_market_cap: u64,
+    quote_config: Option<&QuoteConfig>,
+) -> Result<Option<TokenProject>> {
+    let mut body = account_body(data, &TokenProject::DISCRIMINATOR)?;
+    let v1 = read_field::<TokenProjectV1>(&mut body)?;
+    
+    let version = read_version(&mut body, PROJECT_VERSION)?;
+    if version == PROJECT_VERSION {
+        return Ok(None);
+    }
+    
+    // Projects created before accounts were sized properly may exceed today's caps
+    if v1.name.len() > MAX_NAME_LEN || v1.symbol.len() > MAX_SYMBOL_LEN || v1.curve_params.len() > MAX_CURVE_PARAMS {
+        return Err(error!(ErrorCode::UnsupportedAccountVersion));
+    }
+    
+    // Fields a version did not have yet keep the behaviour the account had before it
+    let mut project = TokenProject {
+        mint: v1.mint,
+        creator: v1.creator,
+        name: v1.name,
+        symbol: v1.symbol,
+        initial_price: v1.initial_price,
+        current_price: v1.current_price,
+        supply: v1.supply,
+        reserve_balance: v1.reserve_balance,
+        curve_params: v1.curve_params,
+        is_graduated: v1.is_graduated,
+        liquidity_pool: v1.liquidity_pool,
+        creator_fee_earned: v1.creator_fee_earned,
+        platform_fee_earned: v1.platform_fee_earned,
+        version: PROJECT_VERSION,
+        // v2: sniper detection and trading limits
+        launch_slot: added_in(&mut body, version, 2, 0)?,
+        max_wallet_bps: added_in(&mut body, version, 2, None)?,
+        max_buy_per_tx: added_in(&mut body, version, 2, None)?,
+        buy_cooldown_secs: added_in(&mut body, version, 2, None)?,
+        // v3: quote tokens
+        quote_mint: added_in(&mut body, version, 3, None)?,
+        quote_decimals: added_in(&mut body, version, 3, SOL_DECIMALS)?,
+        // v4: creator allocation
+        creator_allocation: added_in(&mut body, version, 4, 0)?,
+        // v5: vesting allocations, every earlier token was sold through the curve
+        premint_supply: added_in(&mut body, version, 5, 0)?,
+        // v6: scheduled launches, earlier projects are already open to everyone
+        launch_at: added_in(&mut body, version, 6, 0)?,
+        public_open_at: added_in(&mut body, version, 6, 0)?,
+        whitelist_root: added_in(&mut body, version, 6, None)?,
+        whitelist_cap: added_in(&mut body, version, 6, 0)?,
+        // v7: launch fee curves
+        launch_fee: added_in(&mut body, version, 7, None)?,
+        // v8: refundable launches
+        min_raise: added_in(&mut body, version, 8, None)?,
+        deadline: added_in(&mut body, version, 8, 0)?,
+        min_raise_met: added_in(&mut body, version, 8, false)?,
+        has_third_party_buys: added_in(&mut body, version, 8, false)?,
+        is_cancelled: added_in(&mut body, version, 8, false)?,
+        // v9: graduation caps per project, filled in below
+        graduation_market_cap: added_in(&mut body, version, 9, 0)?,
+    };
+    
+    // Earlier projects measured graduation against the launchpad's SOL figure
+    if version < 9 {
+        project.graduation_market_cap = match project.quote_mint {
+            Some(quote_mint) => quote_graduation_market_cap(quote_config, &quote_mint)?,
+            None => graduation_market_cap,
+        };
+    }
+    
+    Ok(Some(project))
+}
+
+/// Read the version byte that follows the v1 fields. v1 accounts either end before
+/// it or have zeroed slack where it would be, so both read as version 1.
+fn read_version(body: &mut &[u8], current: u8) -> Result<u8> {
+    let version = match body.first() {
+        Some(&version) => {
+            *body = &body[1..];
+            version.max(1)
+        }
+        None => 1,
+    };
+    
+    if version > current {
+        return Err(error!(ErrorCode::UnsupportedAccountVersion));
+    }
+    
+    Ok(version)
+}
+
+/// Read a field appended in layout version `added`, or fall back to `default` if
+/// the account predates it
+fn added_in<T: AnchorDeserialize>(body: &mut &[u8], version: u8, added: u8, default: T) -> Result<T> {
+    if version < added {
+        return Ok(default);
+    }
+    
+    read_field(body)
+}
+
+fn read_field<T: AnchorDeserialize>(body: &mut &[u8]) -> Result<T> {
+    T::deserialize(body).map_err(|_| error!(anchor_lang::error::ErrorCode::AccountDidNotDeserialize))
+}
+
+/// Resize an account to `space`, top up its rent from `payer` and write the upgraded data
+pub fn write_migrated_account<'info, T: AccountSerialize>(
+    account_info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program_info: &AccountInfo<'info>,
+    account: &T,
+    space: usize,
+) -> Result<()> {
+    let rent_exempt_lamports = Rent::get()?.minimum_balance(space);
+    let lamports = account_info.lamports();
+    
+    if rent_exempt_lamports > lamports {
+        system_program::transfer(
+            CpiContext::new(
+                system_program_info.clone(),
+                system_program::Transfer {
+                    from: payer.clone(),
+                    to: account_info.clone(),
+                },
+            ),
+            rent_exempt_lamports - lamports,
+        )?;
+    }
+    
+    // Zero the new bytes so stale data never reads as a later version
+    account_info.realloc(space, true)?;
+    
+    let mut data = account_info.try_borrow_mut_data()?;
+    account.try_serialize(&mut &mut data[..])?;
+    
+    Ok(())
+}
+
+/// Strip and check the account discriminator
+fn account_body<'a>(data: &'a [u8], discriminator: &[u8; 8]) -> Result<&'a [u8]> {
+    if data.len() < 8 || data[..8] != discriminator[..] {
+        return Err(error!(anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch));
+    }
+    
+    Ok(&data[8..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FeeTier, LaunchFeeCurve, LaunchLedger};
+    use crate::bond_curve::{calculate_buy_price, calculate_sell_price};
+    use crate::launch_schedule::{launch_phase, LaunchPhase};
+    use crate::sniper_detection::{init_launch_ledger, is_in_snipe_window, record_launch_buy, LAUNCH_LEDGER_SLOTS};
+    use crate::trading_limits::{check_max_buy, check_max_wallet};
+
+    /// A current config with every field away from its migration default
+    fn config_fixture() -> LaunchpadConfig {
+        LaunchpadConfig {
+            authority: Pubkey::new_unique(),
+            fee_recipient: Pubkey::new_unique(),
+            bundle_threshold_percentage: 500,
+            graduation_market_cap: 100_000,
+            trading_fee_bps: 100,
+            relationship_threshold: 300,
+            version: CONFIG_VERSION,
+            bundle_penalty_mode: BundlePenaltyMode::Confiscate,
+            funding_window_slots: 300,
+            bundle_flag_duration: 7 * 24 * 60 * 60,
+            snipe_window_slots: 10,
+            snipe_tax_bps: 2500,
+            snipe_block: true,
+            referral_fee_bps: 2000,
+            fee_tiers: vec![FeeTier { min_volume: 1_000_000, fee_bps: 50 }],
+        }
+    }
+
+    /// A current project with every field away from its migration default
+    fn project_fixture() -> TokenProject {
+        TokenProject {
+            mint: Pubkey::new_unique(),
+            creator: Pubkey::new_unique(),
+            name: "Organic".to_string(),
+            symbol: "ORG".to_string(),
+            initial_price: 1_000,
+            current_price: 2_500,
+            supply: 1_000_000,
+            reserve_balance: 5_000_000,
+            curve_params: vec![1, 2, 3],
+            is_graduated: false,
+            liquidity_pool: None,
+            creator_fee_earned: 10,
+            platform_fee_earned: 10,
+            version: PROJECT_VERSION,
+            launch_slot: 42,
+            max_wallet_bps: Some(200),
+            max_buy_per_tx: Some(50_000),
+            buy_cooldown_secs: Some(30),
+            quote_mint: Some(Pubkey::new_unique()),
+            quote_decimals: 6,
+            creator_allocation: 50_000,
+            premint_supply: 250_000,
+            launch_at: 1_700_000_000,
+            public_open_at: 1_700_000_600,
+            whitelist_root: Some([7; 32]),
+            whitelist_cap: 10_000,
+            launch_fee: Some(LaunchFeeCurve { start_fee_bps: 5000, decay_slots: 150 }),
+            min_raise: Some(1_000_000_000),
+            deadline: 1_700_086_400,
+            min_raise_met: true,
+            has_third_party_buys: true,
+            is_cancelled: true,
+            graduation_market_cap: 69_000_000_000,
+        }
+    }
+
+    fn quote_config_fixture(project: &TokenProject) -> QuoteConfig {
+        QuoteConfig {
+            quote_mint: project.quote_mint.unwrap(),
+            graduation_market_cap: 42_000_000,
+        }
+    }
+
+    fn config_v1(config: &LaunchpadConfig) -> LaunchpadConfigV1 {
+        LaunchpadConfigV1 {
+            authority: config.authority,
+            fee_recipient: config.fee_recipient,
+            bundle_threshold_percentage: config.bundle_threshold_percentage,
+            graduation_market_cap: config.graduation_market_cap,
+            trading_fee_bps: config.trading_fee_bps,
+            relationship_threshold: config.relationship_threshold,
+        }
+    }
+
+    fn project_v1(project: &TokenProject) -> TokenProjectV1 {
+        TokenProjectV1 {
+            mint: project.mint,
+            creator: project.creator,
+            name: project.name.clone(),
+            symbol: project.symbol.clone(),
+            initial_price: project.initial_price,
+            current_price: project.current_price,
+            supply: project.supply,
+            reserve_balance: project.reserve_balance,
+            curve_params: project.curve_params.clone(),
+            is_graduated: project.is_graduated,
+            liquidity_pool: project.liquidity_pool,
+            creator_fee_earned: project.creator_fee_earned,
+            platform_fee_earned: project.platform_fee_earned,
+        }
+    }
+
+    /// Serialized fields a config version appended
+    fn config_fields_added_in(version: u8, config: &LaunchpadConfig) -> Vec<u8> {
+        match version {
+            2 => (
+                config.bundle_penalty_mode,
+                config.funding_window_slots,
+                config.bundle_flag_duration,
+                config.snipe_window_slots,
+                config.snipe_tax_bps,
+                config.snipe_block,
+            ).try_to_vec(),
+            3 => config.referral_fee_bps.try_to_vec(),
+            4 => config.fee_tiers.try_to_vec(),
+            _ => unreachable!(),
+        }.unwrap()
+    }
+
+    /// Serialized fields a project version appended
+    fn project_fields_added_in(version: u8, project: &TokenProject) -> Vec<u8> {
+        match version {
+            2 => (
+                project.launch_slot,
+                project.max_wallet_bps,
+                project.max_buy_per_tx,
+                project.buy_cooldown_secs,
+            ).try_to_vec(),
+            3 => (project.quote_mint, project.quote_decimals).try_to_vec(),
+            4 => project.creator_allocation.try_to_vec(),
+            5 => project.premint_supply.try_to_vec(),
+            6 => (
+                project.launch_at,
+                project.public_open_at,
+                project.whitelist_root,
+                project.whitelist_cap,
+            ).try_to_vec(),
+            7 => project.launch_fee.try_to_vec(),
+            8 => (
+                project.min_raise,
+                project.deadline,
+                project.min_raise_met,
+                project.has_third_party_buys,
+                project.is_cancelled,
+            ).try_to_vec(),
+            9 => project.graduation_market_cap.try_to_vec(),
+            _ => unreachable!(),
+        }.unwrap()
+    }
+
+    /// Raw account data as a version wrote it on-chain: the v1 fields, the version byte
+    /// and the fields added up to that version, then optional zeroed slack
+    fn account_data<T: AnchorSerialize>(
+        discriminator: &[u8; 8],
+        v1: &T,
+        version: u8,
+        fields_added_in: impl Fn(u8) -> Vec<u8>,
+        slack: usize,
+    ) -> Vec<u8> {
+        let mut data = discriminator.to_vec();
+        data.extend(v1.try_to_vec().unwrap());
+        if version > 1 {
+            data.push(version);
+            for added in 2..=version {
+                data.extend(fields_added_in(added));
+            }
+        }
+        data.extend(vec![0; slack]);
+        data
+    }
+
+    #[test]
+    fn migrates_every_version() {
+        let config = config_fixture();
+        let project = project_fixture();
+        let quote_config = quote_config_fixture(&project);
+        
+        // (version, zeroed slack after the account data)
+        let cases = [(1, 0), (1, 64), (2, 0), (3, 8), (4, 0), (5, 0), (6, 32), (7, 0), (8, 0), (9, 0)];
+        
+        for (version, slack) in cases {
+            if version <= CONFIG_VERSION {
+                let data = account_data(
+                    &LaunchpadConfig::DISCRIMINATOR,
+                    &config_v1(&config),
+                    version,
+                    |added| config_fields_added_in(added, &config),
+                    slack,
+                );
+                
+                match upgrade_config(&data).unwrap() {
+                    None => {
+                        assert_eq!(version, CONFIG_VERSION);
+                        assert_eq!(data[8..], config.try_to_vec().unwrap()[..]);
+                    }
+                    Some(upgraded) => {
+                        assert_eq!(upgraded.version, CONFIG_VERSION);
+                        assert_eq!(upgraded.authority, config.authority);
+                        assert_eq!(upgraded.relationship_threshold, config.relationship_threshold);
+                        
+                        // Fields the account had are kept, later ones are defaulted
+                        for added in 2..=CONFIG_VERSION {
+                            let kept = config_fields_added_in(added, &upgraded) == config_fields_added_in(added, &config);
+                            assert_eq!(kept, added <= version, "config v{} field group v{}", version, added);
+                        }
+                    }
+                }
+            }
+            
+            let data = account_data(
+                &TokenProject::DISCRIMINATOR,
+                &project_v1(&project),
+                version,
+                |added| project_fields_added_in(added, &project),
+                slack,
+            );
+            
+            match upgrade_project(&data, config.graduation_market_cap, Some(&quote_config)).unwrap() {
+                None => {
+                    assert_eq!(version, PROJECT_VERSION);
+                    assert_eq!(data[8..], project.try_to_vec().unwrap()[..]);
+                }
+                Some(upgraded) => {
+                    assert_eq!(upgraded.version, PROJECT_VERSION);
+                    assert_eq!(upgraded.mint, project.mint);
+                    assert_eq!(upgraded.name, project.name);
+                    assert_eq!(upgraded.curve_params, project.curve_params);
+                    
+                    for added in 2..=PROJECT_VERSION {
+                        let kept = project_fields_added_in(added, &upgraded) == project_fields_added_in(added, &project);
+                        assert_eq!(kept, added <= version, "project v{} field group v{}", version, added);
+                    }
+                    
+                    if version < 3 {
+                        assert_eq!(upgraded.quote_mint, None);
+                        assert_eq!(upgraded.quote_decimals, SOL_DECIMALS);
+                        assert_eq!(upgraded.graduation_market_cap, config.graduation_market_cap);
+                    } else if version < 9 {
+                        assert_eq!(upgraded.graduation_market_cap, quote_config.graduation_market_cap);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn v1_projects_can_be_bought_once_migrated() {
+        let mut project = project_fixture();
+        project.curve_params = vec![10_000, 1_000];
+        let data = account_data(&TokenProject::DISCRIMINATOR, &project_v1(&project), 1, |_| Vec::new(), 0);
+        let mut upgraded = upgrade_project(&data, 100_000, None).unwrap().unwrap();
+        
+        // The launch ledger migrate_project creates next to the upgraded project
+        let mut launch_ledger = LaunchLedger {
+            mint: Pubkey::default(),
+            launch_slot: 0,
+            buy_counts: [0; LAUNCH_LEDGER_SLOTS],
+            buy_volumes: [0; LAUNCH_LEDGER_SLOTS],
+        };
+        init_launch_ledger(&mut launch_ledger, upgraded.mint, upgraded.launch_slot);
+        
+        // A buy long after launch passes every check and records nothing in the ledger
+        let (now, slot, amount) = (1_700_000_000, 250_000_000, 10_000);
+        assert_eq!(launch_phase(&upgraded, now), LaunchPhase::Public);
+        assert!(!upgraded.is_cancelled);
+        assert!(!is_in_snipe_window(upgraded.launch_slot, slot, 10));
+        check_max_buy(&upgraded, amount).unwrap();
+        check_max_wallet(&upgraded, amount).unwrap();
+        
+        let price = calculate_buy_price(&upgraded.curve_params, upgraded.supply, amount).unwrap();
+        upgraded.supply += amount;
+        assert_eq!(calculate_sell_price(&upgraded.curve_params, upgraded.supply, amount).unwrap(), price);
+        
+        record_launch_buy(&mut launch_ledger, slot, amount).unwrap();
+        assert_eq!(launch_ledger.mint, project.mint);
+        assert!(launch_ledger.buy_counts.iter().all(|&count| count == 0));
+    }
+
+    #[test]
+    fn quote_projects_need_their_quote_cap_to_migrate() {
+        let project = project_fixture();
+        let data = account_data(
+            &TokenProject::DISCRIMINATOR,
+            &project_v1(&project),
+            8,
+            |added| project_fields_added_in(added, &project),
+            0,
+        );
+        
+        assert!(upgrade_project(&data, 100_000, None).is_err());
+        
+        let other_quote = QuoteConfig { quote_mint: Pubkey::new_unique(), graduation_market_cap: 1 };
+        assert!(upgrade_project(&data, 100_000, Some(&other_quote)).is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_accounts() {
+        let project = project_fixture();
+        
+        let data = account_data(&LaunchpadConfig::DISCRIMINATOR, &project_v1(&project), 1, |_| Vec::new(), 0);
+        assert!(upgrade_project(&data, 100_000, None).is_err());
+        
+        let data = account_data(&TokenProject::DISCRIMINATOR, &project_v1(&project), PROJECT_VERSION + 1, |_| Vec::new(), 0);
+        assert!(upgrade_project(&data, 100_000, None).is_err());
+    }
+}
@@ -0,0 +1,181 @@
+use anchor_lang::prelude::*;
+
+use crate::{ErrorCode, Order, OrderSide, TokenProject};
+use crate::launch_schedule::{launch_phase, LaunchPhase, SCHEDULED_LAUNCH_SLOT};
+use crate::refunds::is_cancelled;
+use crate::sniper_detection::{calculate_launch_fee_bps, is_in_snipe_window};
+
+/// Check a limit order before its funds are escrowed
+pub fn validate_order(
+    side: OrderSide,
+    trigger_price: u64,
+    amount: u64,
+    max_sol_cost: Option<u64>,
+    min_proceeds: Option<u64>,
+    expires_at: i64,
+    now: i64,
+) -> Result<()> {
+    if trigger_price == 0 || amount == 0 || expires_at <= now {
+        return Err(error!(ErrorCode::InvalidOrder));
+    }
+    
+    // Buys escrow the most they are willing to pay
+    if side == OrderSide::Buy && !matches!(max_sol_cost, Some(max_sol_cost) if max_sol_cost > 0) {
+        return Err(error!(ErrorCode::InvalidOrder));
+    }
+    
+    // Only sells pay out proceeds
+    if side == OrderSide::Buy && min_proceeds.is_some() {
+        return Err(error!(ErrorCode::InvalidOrder));
+    }
+    
+    Ok(())
+}
+
+/// What a new order escrows for its owner, and the lamports moved onto the order. Buys
+/// escrow their max cost in lamports; sells escrow their tokens, which are burned when
+/// the order is placed since the mint's transfer hook is this program and a transfer
+/// would re-enter it. The keeper fee is always held in lamports.
+pub fn order_escrow(side: OrderSide, amount: u64, max_sol_cost: Option<u64>, keeper_fee: u64) -> Result<(u64, u64)> {
+    match side {
+        OrderSide::Buy => {
+            let escrowed = max_sol_cost.unwrap_or(0);
+            let escrowed_lamports = escrowed.checked_add(keeper_fee).ok_or(error!(ErrorCode::MathOverflow))?;
+            Ok((escrowed, escrowed_lamports))
+        }
+        OrderSide::Sell => Ok((amount, keeper_fee)),
+    }
+}
+
+/// Take the tokens a sell order escrowed, leaving none behind. A cancel mints them
+/// back to the owner, an execution sells them against the curve. Buys hold no tokens.
+pub fn take_escrowed_tokens(order: &mut Order) -> u64 {
+    match order.side {
+        OrderSide::Buy => 0,
+        OrderSide::Sell => std::mem::take(&mut order.escrowed),
+    }
+}
+
+/// Whether the project price has crossed the order's trigger. Buys fill at or below
+/// the trigger price, sells at or above it.
+pub fn is_triggered(order: &Order, current_price: u64) -> bool {
+    match order.side {
+        OrderSide::Buy => current_price <= order.trigger_price,
+        OrderSide::Sell => current_price >= order.trigger_price,
+    }
+}
+
+/// Check that an order can be executed now. Orders only fill once public trading is
+/// open, the sniping window has closed and the launch fee has decayed, so keepers
+/// cannot snipe a launch with them.
+pub fn check_executable(
+    order: &Order,
+    project: &TokenProject,
+    now: i64,
+    slot: u64,
+    snipe_window_slots: u64,
+    trading_fee_bps: u16,
+) -> Result<()> {
+    if now >= order.expires_at {
+        return Err(error!(ErrorCode::OrderExpired));
+    }
+    
+    if is_cancelled(project, now) {
+        return Err(error!(ErrorCode::ProjectCancelled));
+    }
+    
+    if launch_phase(project, now) != LaunchPhase::Public
+        || project.launch_slot == SCHEDULED_LAUNCH_SLOT
+        || is_in_snipe_window(project.launch_slot, slot, snipe_window_slots)
+        || calculate_launch_fee_bps(project.launch_fee.as_ref(), project.launch_slot, slot, trading_fee_bps) > trading_fee_bps
+    {
+        return Err(error!(ErrorCode::OrderNotTriggered));
+    }
+    
+    if !is_triggered(order, project.current_price) {
+        return Err(error!(ErrorCode::OrderNotTriggered));
+    }
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::project_fixture;
+
+    fn order_fixture(side: OrderSide, trigger_price: u64) -> Order {
+        Order {
+            mint: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            order_id: 0,
+            side,
+            trigger_price,
+            amount: 100,
+            escrowed: 0,
+            min_proceeds: 0,
+            keeper_fee: 0,
+            expires_at: i64::MAX,
+            created_at: 0,
+        }
+    }
+
+    #[test]
+    fn buys_trigger_at_or_below_the_trigger_price() {
+        let order = order_fixture(OrderSide::Buy, 1_000);
+        
+        assert!(is_triggered(&order, 999));
+        assert!(is_triggered(&order, 1_000));
+        assert!(!is_triggered(&order, 1_001));
+    }
+
+    #[test]
+    fn sells_trigger_at_or_above_the_trigger_price() {
+        let order = order_fixture(OrderSide::Sell, 1_000);
+        
+        assert!(!is_triggered(&order, 999));
+        assert!(is_triggered(&order, 1_000));
+        assert!(is_triggered(&order, 1_001));
+    }
+
+    #[test]
+    fn sell_orders_hold_their_tokens_until_cancelled_or_executed() {
+        // Placing a sell escrows its tokens and only the keeper fee in lamports
+        let (escrowed, escrowed_lamports) = order_escrow(OrderSide::Sell, 100, None, 5_000).unwrap();
+        assert_eq!((escrowed, escrowed_lamports), (100, 5_000));
+        
+        let mut order = order_fixture(OrderSide::Sell, 1_000);
+        order.escrowed = escrowed;
+        
+        // Cancelling gives every escrowed token back, once
+        let mut cancelled = order.clone();
+        assert_eq!(take_escrowed_tokens(&mut cancelled), 100);
+        assert_eq!(take_escrowed_tokens(&mut cancelled), 0);
+        
+        // Executing waits for the price to reach the trigger, then sells the escrow
+        let mut project = project_fixture();
+        project.launch_slot = 1;
+        project.current_price = 999;
+        assert!(check_executable(&order, &project, 0, 1_000, 0, 100).is_err());
+        
+        project.current_price = 1_000;
+        assert!(check_executable(&order, &project, 0, 1_000, 0, 100).is_ok());
+        assert_eq!(take_escrowed_tokens(&mut order), order.amount);
+    }
+
+    #[test]
+    fn buy_orders_escrow_lamports_only() {
+        assert_eq!(order_escrow(OrderSide::Buy, 100, Some(150_000), 5_000).unwrap(), (150_000, 155_000));
+        
+        let mut order = order_fixture(OrderSide::Buy, 1_000);
+        order.escrowed = 150_000;
+        assert_eq!(take_escrowed_tokens(&mut order), 0);
+        assert_eq!(order.escrowed, 150_000);
+    }
+
+    #[test]
+    fn min_proceeds_only_applies_to_sells() {
+        assert!(validate_order(OrderSide::Sell, 1_000, 100, None, Some(50_000), 10, 0).is_ok());
+        assert!(validate_order(OrderSide::Buy, 1_000, 100, Some(150_000), Some(50_000), 10, 0).is_err());
+    }
+}
@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::{ErrorCode, QuoteConfig, TokenProject};
+
+/// Accounts for the quote-token leg of a trade on a project priced in an SPL token
+pub struct QuoteAccounts<'a, 'info> {
+    pub mint: &'a InterfaceAccount<'info, Mint>,
+    pub vault: &'a InterfaceAccount<'info, TokenAccount>,
+    pub trader_account: &'a InterfaceAccount<'info, TokenAccount>,
+    pub creator_account: &'a InterfaceAccount<'info, TokenAccount>,
+    pub fee_account: &'a InterfaceAccount<'info, TokenAccount>,
+    pub token_program: &'a Interface<'info, TokenInterface>,
+}
+
+/// Collect and check the optional quote accounts of a trade.
+///
+/// The vault must belong to the project, the fee account to the launchpad fee vault,
+/// the creator account to the project creator and the trader account to the trader.
+pub fn require_quote_accounts<'a, 'info>(
+    project: &TokenProject,
+    project_key: &Pubkey,
+    fee_vault: &Pubkey,
+    trader: &Pubkey,
+    mint: &'a Option<InterfaceAccount<'info, Mint>>,
+    vault: &'a Option<InterfaceAccount<'info, TokenAccount>>,
+    trader_account: &'a Option<InterfaceAccount<'info, TokenAccount>>,
+    creator_account: &'a Option<InterfaceAccount<'info, TokenAccount>>,
+    fee_account: &'a Option<InterfaceAccount<'info, TokenAccount>>,
+    token_program: &'a Option<Interface<'info, TokenInterface>>,
+) -> Result<QuoteAccounts<'a, 'info>> {
+    let quote = QuoteAccounts {
+        mint: mint.as_ref().ok_or(error!(ErrorCode::QuoteAccountsRequired))?,
+        vault: vault.as_ref().ok_or(error!(ErrorCode::QuoteAccountsRequired))?,
+        trader_account: trader_account.as_ref().ok_or(error!(ErrorCode::QuoteAccountsRequired))?,
+        creator_account: creator_account.as_ref().ok_or(error!(ErrorCode::QuoteAccountsRequired))?,
+        fee_account: fee_account.as_ref().ok_or(error!(ErrorCode::QuoteAccountsRequired))?,
+        token_program: token_program.as_ref().ok_or(error!(ErrorCode::QuoteAccountsRequired))?,
+    };
+
+    let quote_mint = quote.mint.key();
+    if project.quote_mint != Some(quote_mint) {
+        return Err(error!(ErrorCode::QuoteAccountMismatch));
+    }
+
+    let owners = [
+        (quote.vault, project_key),
+        (quote.trader_account, trader),
+        (quote.creator_account, &project.creator),
+        (quote.fee_account, fee_vault),
+    ];
+
+    for (account, owner) in owners {
+        if account.mint != quote_mint || account.owner != *owner {
+            return Err(error!(ErrorCode::QuoteAccountMismatch));
+        }
+    }
+
+    Ok(quote)
+}
+
+/// Graduation market cap the authority configured for a quote mint, in its base units.
+/// Quote-token launches cannot open, or migrate, until their quote mint has one.
+pub fn quote_graduation_market_cap(quote_config: Option<&QuoteConfig>, quote_mint: &Pubkey) -> Result<u64> {
+    let quote_config = quote_config.ok_or(error!(ErrorCode::QuoteNotConfigured))?;
+    if quote_config.quote_mint != *quote_mint {
+        return Err(error!(ErrorCode::QuoteAccountMismatch));
+    }
+
+    Ok(quote_config.graduation_market_cap)
+}
+
+/// Move quote tokens with `transfer_checked`, signing with `signer_seeds` when the
+/// source is owned by a PDA
+pub fn transfer_quote<'info>(
+    quote: &QuoteAccounts<'_, 'info>,
+    from: &InterfaceAccount<'info, TokenAccount>,
+    to: &InterfaceAccount<'info, TokenAccount>,
+    authority: AccountInfo<'info>,
+    amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            quote.token_program.to_account_info(),
+            TransferChecked {
+                from: from.to_account_info(),
+                mint: quote.mint.to_account_info(),
+                to: to.to_account_info(),
+                authority,
+            },
+            signer_seeds,
+        ),
+        amount,
+        quote.mint.decimals,
+    )
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::{ErrorCode, Referrer};
+
+/// Maximum referral share of the trading fee (in basis points). Referral fees are
+/// carved from the platform half of the fee, so they can never exceed it.
+pub const MAX_REFERRAL_FEE_BPS: u16 = 5000;
+
+/// Calculate the referrer's cut of a trade's total fee, capped at the platform share
+pub fn calculate_referral_fee(total_fee: u64, platform_fee: u64, referral_fee_bps: u16) -> Result<u64> {
+    let referral_fee = total_fee
+        .checked_mul(referral_fee_bps as u64)
+        .ok_or(error!(ErrorCode::MathOverflow))?
+        .checked_div(10000)
+        .ok_or(error!(ErrorCode::DivisionByZero))?;
+
+    Ok(referral_fee.min(platform_fee))
+}
+
+/// Credit a referred trade to the referrer's account
+pub fn record_referral(referrer: &mut Referrer, volume: u64, referral_fee: u64) -> Result<()> {
+    referrer.total_volume = referrer.total_volume
+        .checked_add(volume)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+    referrer.total_fees_earned = referrer.total_fees_earned
+        .checked_add(referral_fee)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+    referrer.fees_claimable = referrer.fees_claimable
+        .checked_add(referral_fee)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+    referrer.last_updated = Clock::get()?.unix_timestamp;
+
+    Ok(())
+}
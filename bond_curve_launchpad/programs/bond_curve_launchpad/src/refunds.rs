@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+
+use crate::{ErrorCode, LaunchCancelled, MinRaise, TokenProject};
+use crate::launch_schedule::{launch_phase, LaunchPhase};
+
+/// Check a minimum raise before it is stored on the project. The deadline must fall
+/// after trading opens so buyers get a chance to reach it.
+pub fn validate_min_raise(min_raise: &MinRaise, opens_at: i64) -> Result<()> {
+    if min_raise.amount == 0 || min_raise.deadline <= opens_at {
+        return Err(error!(ErrorCode::InvalidMinRaise));
+    }
+    
+    Ok(())
+}
+
+/// Mark the minimum raise as met once the reserve reaches it. Trades are rejected
+/// once a launch has failed, so this only runs before the deadline or after success.
+pub fn record_raise_progress(project: &mut TokenProject) {
+    if matches!(project.min_raise, Some(min_raise) if project.reserve_balance >= min_raise) {
+        project.min_raise_met = true;
+    }
+}
+
+/// Whether the project missed its minimum raise. Launches that have not opened yet,
+/// e.g. an unsettled auction, cannot fail.
+pub fn has_failed(project: &TokenProject, now: i64) -> bool {
+    project.min_raise.is_some()
+        && !project.min_raise_met
+        && now >= project.deadline
+        && launch_phase(project, now) != LaunchPhase::Pending
+}
+
+/// Whether the project is closed to trading and open to refunds
+pub fn is_cancelled(project: &TokenProject, now: i64) -> bool {
+    project.is_cancelled || has_failed(project, now)
+}
+
+/// Move the project to Cancelled
+pub fn cancel_project(project: &mut TokenProject, cancelled_by: &Pubkey, by_creator: bool, now: i64) {
+    project.is_cancelled = true;
+    
+    emit!(LaunchCancelled {
+        mint: project.mint,
+        cancelled_by: *cancelled_by,
+        by_creator,
+        reserve_balance: project.reserve_balance,
+        supply: project.supply,
+        timestamp: now,
+    });
+}
+
+/// Share of the reserve paid for burning `amount` tokens of a cancelled project
+pub fn calculate_refund(project: &TokenProject, amount: u64) -> Result<u64> {
+    if amount > project.supply {
+        return Err(error!(ErrorCode::InsufficientSupply));
+    }
+    
+    let refund = (project.reserve_balance as u128)
+        .checked_mul(amount as u128)
+        .ok_or(error!(ErrorCode::MathOverflow))?
+        .checked_div(project.supply as u128)
+        .ok_or(error!(ErrorCode::DivisionByZero))?;
+    
+    Ok(refund as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::project_fixture;
+
+    #[test]
+    fn refunds_an_equal_share_of_the_reserve() {
+        let mut project = project_fixture();
+        project.supply = 3;
+        project.reserve_balance = 1_000;
+        
+        // Each token is worth 333.33 lamports; shares round down
+        assert_eq!(calculate_refund(&project, 1).unwrap(), 333);
+        assert_eq!(calculate_refund(&project, 2).unwrap(), 666);
+        assert_eq!(calculate_refund(&project, 3).unwrap(), 1_000);
+        assert!(calculate_refund(&project, 4).is_err());
+    }
+
+    #[test]
+    fn rounding_remainders_stay_in_the_reserve() {
+        let mut project = project_fixture();
+        project.supply = 3;
+        project.reserve_balance = 1_000;
+        
+        // Burning one token at a time pays out at most the reserve
+        let mut paid = 0;
+        while project.supply > 0 {
+            let refund = calculate_refund(&project, 1).unwrap();
+            project.supply -= 1;
+            project.reserve_balance -= refund;
+            paid += refund;
+        }
+        assert_eq!(paid, 1_000);
+        assert_eq!(project.reserve_balance, 0);
+    }
+
+    #[test]
+    fn launches_fail_after_the_deadline_without_the_minimum_raise() {
+        let mut project = project_fixture();
+        project.min_raise = Some(1_000);
+        project.deadline = 100;
+        
+        assert!(!has_failed(&project, 99));
+        assert!(has_failed(&project, 100));
+        
+        project.reserve_balance = 1_000;
+        record_raise_progress(&mut project);
+        assert!(!has_failed(&project, 100));
+    }
+}
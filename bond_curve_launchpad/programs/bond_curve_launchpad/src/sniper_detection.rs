@@ -0,0 +1,130 @@
+use anchor_lang::prelude::*;
+
+use crate::{BundleCluster, ErrorCode, LaunchFeeCurve, LaunchLedger};
+
+/// Number of slots after launch covered by the launch ledger
+pub const LAUNCH_LEDGER_SLOTS: usize = 32;
+
+/// Cluster id reserved for the wallets that buy inside a project's sniping window
+pub const SNIPER_CLUSTER_ID: u64 = u64::MAX;
+
+/// Check if a buy at `slot` falls inside the sniping window that opens at `launch_slot`
+pub fn is_in_snipe_window(launch_slot: u64, slot: u64, snipe_window_slots: u64) -> bool {
+    if snipe_window_slots == 0 || slot < launch_slot {
+        return false;
+    }
+    
+    slot - launch_slot < snipe_window_slots
+}
+
+/// Start the per-slot buy ledger for a launch window that opens at `launch_slot`
+pub fn init_launch_ledger(launch_ledger: &mut LaunchLedger, mint: Pubkey, launch_slot: u64) {
+    launch_ledger.mint = mint;
+    launch_ledger.launch_slot = launch_slot;
+    launch_ledger.buy_counts = [0; LAUNCH_LEDGER_SLOTS];
+    launch_ledger.buy_volumes = [0; LAUNCH_LEDGER_SLOTS];
+}
+
+/// Start the cluster that wallets buying inside the sniping window are tagged into
+pub fn init_sniper_cluster(sniper_cluster: &mut BundleCluster, mint: Pubkey, snipe_block: bool, now: i64) {
+    sniper_cluster.mint = mint;
+    sniper_cluster.cluster_id = SNIPER_CLUSTER_ID;
+    sniper_cluster.member_count = 0;
+    sniper_cluster.total_balance = 0;
+    sniper_cluster.force_bundling = snipe_block;
+    sniper_cluster.is_bundling = snipe_block;
+    sniper_cluster.last_updated = now;
+    sniper_cluster.last_buy_at = 0;
+}
+
+/// Record a buy in the launch ledger if it lands in the first slots after launch
+pub fn record_launch_buy(
+    launch_ledger: &mut LaunchLedger,
+    slot: u64,
+    amount: u64
+) -> Result<()> {
+    let offset = match slot.checked_sub(launch_ledger.launch_slot) {
+        Some(offset) if offset < LAUNCH_LEDGER_SLOTS as u64 => offset as usize,
+        _ => return Ok(()),
+    };
+    
+    launch_ledger.buy_counts[offset] = launch_ledger.buy_counts[offset]
+        .checked_add(1)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+    launch_ledger.buy_volumes[offset] = launch_ledger.buy_volumes[offset]
+        .checked_add(amount)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+    
+    Ok(())
+}
+
+/// Calculate the extra tax charged on a buy inside the sniping window
+pub fn calculate_snipe_tax(price: u64, snipe_tax_bps: u16) -> Result<u64> {
+    price
+        .checked_mul(snipe_tax_bps as u64)
+        .ok_or(error!(ErrorCode::MathOverflow))?
+        .checked_div(10000)
+        .ok_or(error!(ErrorCode::DivisionByZero))
+}
+
+/// Check a project's launch fee curve
+pub fn validate_launch_fee(launch_fee: &LaunchFeeCurve) -> Result<()> {
+    if launch_fee.start_fee_bps > 10000 || launch_fee.decay_slots == 0 {
+        return Err(error!(ErrorCode::InvalidLaunchFee));
+    }
+    
+    Ok(())
+}
+
+/// Fee in basis points for a buy at `slot`. The launch fee starts at `start_fee_bps`
+/// in the launch slot and decays linearly to `base_fee_bps` over `decay_slots`; it
+/// never drops a buy below the base fee.
+pub fn calculate_launch_fee_bps(
+    launch_fee: Option<&LaunchFeeCurve>,
+    launch_slot: u64,
+    slot: u64,
+    base_fee_bps: u16
+) -> u16 {
+    let launch_fee = match launch_fee {
+        Some(launch_fee) if launch_fee.start_fee_bps > base_fee_bps => launch_fee,
+        _ => return base_fee_bps,
+    };
+    
+    let elapsed = match slot.checked_sub(launch_slot) {
+        Some(elapsed) if elapsed < launch_fee.decay_slots => elapsed,
+        _ => return base_fee_bps,
+    };
+    
+    let excess = (launch_fee.start_fee_bps - base_fee_bps) as u128;
+    let remaining_excess = excess * (launch_fee.decay_slots - elapsed) as u128 / launch_fee.decay_slots as u128;
+    
+    base_fee_bps + remaining_excess as u16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn launch_fee_decays_linearly_to_the_base_fee() {
+        let launch_fee = LaunchFeeCurve { start_fee_bps: 5100, decay_slots: 10 };
+        
+        assert_eq!(calculate_launch_fee_bps(Some(&launch_fee), 100, 100, 100), 5100);
+        assert_eq!(calculate_launch_fee_bps(Some(&launch_fee), 100, 105, 100), 2600);
+        // The remaining excess rounds down
+        assert_eq!(calculate_launch_fee_bps(Some(&launch_fee), 100, 107, 100), 1600);
+        assert_eq!(calculate_launch_fee_bps(Some(&launch_fee), 100, 109, 100), 600);
+        assert_eq!(calculate_launch_fee_bps(Some(&launch_fee), 100, 110, 100), 100);
+        assert_eq!(calculate_launch_fee_bps(Some(&launch_fee), 100, u64::MAX, 100), 100);
+    }
+
+    #[test]
+    fn launch_fee_never_drops_below_the_base_fee() {
+        let launch_fee = LaunchFeeCurve { start_fee_bps: 50, decay_slots: 10 };
+        
+        assert_eq!(calculate_launch_fee_bps(Some(&launch_fee), 100, 100, 100), 100);
+        assert_eq!(calculate_launch_fee_bps(None, 100, 100, 100), 100);
+        // Slots before the recorded launch slot pay the base fee
+        assert_eq!(calculate_launch_fee_bps(Some(&LaunchFeeCurve { start_fee_bps: 5100, decay_slots: 10 }), 100, 99, 100), 100);
+    }
+}
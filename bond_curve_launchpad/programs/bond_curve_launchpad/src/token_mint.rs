@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke;
+use anchor_lang::system_program;
+use spl_token_2022::{
+    extension::ExtensionType,
+    state::Mint,
+};
+
+use crate::metadata::initialize_metadata_pointer;
+use crate::transfer_hook::initialize_transfer_hook;
+
+/// Maximum number of decimals for a launchpad mint
+pub const MAX_DECIMALS: u8 = 9;
+
+/// Fixed-size extensions every launchpad mint is created with
+const MINT_EXTENSIONS: [ExtensionType; 4] = [
+    ExtensionType::MintCloseAuthority,
+    ExtensionType::TransferHook,
+    ExtensionType::PermanentDelegate,
+    ExtensionType::MetadataPointer,
+];
+
+/// Create a Token-2022 mint with the launchpad's extensions. The project PDA is
+/// the mint, freeze and close authority, the permanent delegate and the metadata
+/// update authority.
+pub fn create_token_mint<'info>(
+    mint: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    project: &Pubkey,
+    program_id: &Pubkey,
+    decimals: u8,
+    token_program: &AccountInfo<'info>,
+    system_program_info: &AccountInfo<'info>,
+) -> Result<()> {
+    if decimals > MAX_DECIMALS {
+        return Err(error!(ErrorCode::InvalidDecimals));
+    }
+    
+    // Allocate the mint with room for its fixed-size extensions. The variable-length
+    // metadata is funded when it is written.
+    let space = ExtensionType::try_calculate_account_len::<Mint>(&MINT_EXTENSIONS)?;
+    
+    system_program::create_account(
+        CpiContext::new(
+            system_program_info.clone(),
+            system_program::CreateAccount {
+                from: payer.clone(),
+                to: mint.clone(),
+            },
+        ),
+        Rent::get()?.minimum_balance(space),
+        space as u64,
+        token_program.key,
+    )?;
+    
+    // Extensions must be initialized before the mint itself
+    initialize_transfer_hook(mint, project, program_id, project, token_program)?;
+    initialize_metadata_pointer(mint, project, token_program)?;
+    
+    let ix = spl_token_2022::instruction::initialize_mint2(
+        token_program.key,
+        mint.key,
+        project,
+        Some(project),
+        decimals,
+    )?;
+    
+    invoke(
+        &ix,
+        &[
+            mint.clone(),
+            token_program.clone(),
+        ],
+    )?;
+    
+    Ok(())
+}
@@ -0,0 +1,159 @@
+use anchor_lang::prelude::*;
+
+use crate::{ErrorCode, TokenProject};
+
+/// Cap on how many times the base cooldown is multiplied for related wallets
+pub const MAX_COOLDOWN_MULTIPLIER: i64 = 10;
+
+/// Supply the max-wallet limit is measured against.
+///
+/// This is not the current supply: right after launch the circulating supply is tiny and
+/// the first buyer holds all of it. It is the supply the project would need at today's
+/// price to reach its graduation market cap, or the current supply once that is larger.
+/// Without a price there is no graduation supply, so the current supply is used.
+pub fn limit_reference_supply(supply: u64, current_price: u64, graduation_market_cap: u64) -> u64 {
+    if current_price == 0 {
+        return supply;
+    }
+    
+    supply.max(graduation_market_cap / current_price)
+}
+
+/// Check a single buy against the project's per-transaction limit
+pub fn check_max_buy(project: &TokenProject, amount: u64) -> Result<()> {
+    // Limits are lifted once the token graduates
+    if project.is_graduated {
+        return Ok(());
+    }
+    
+    if let Some(max_buy_per_tx) = project.max_buy_per_tx {
+        if amount > max_buy_per_tx {
+            return Err(error!(ErrorCode::MaxBuyExceeded));
+        }
+    }
+    
+    Ok(())
+}
+
+/// Check a wallet's post-trade balance against the project's max-wallet limit
+pub fn check_max_wallet(project: &TokenProject, wallet_balance: u64) -> Result<()> {
+    // Limits are lifted once the token graduates
+    if project.is_graduated {
+        return Ok(());
+    }
+    
+    let max_wallet_bps = match project.max_wallet_bps {
+        Some(max_wallet_bps) => max_wallet_bps,
+        None => return Ok(()),
+    };
+    
+    let reference_supply = limit_reference_supply(project.supply, project.current_price, project.graduation_market_cap);
+    
+    let max_wallet_balance = (reference_supply as u128)
+        .checked_mul(max_wallet_bps as u128)
+        .ok_or(error!(ErrorCode::MathOverflow))?
+        .checked_div(10000)
+        .ok_or(error!(ErrorCode::DivisionByZero))?;
+    
+    if wallet_balance as u128 > max_wallet_balance {
+        return Err(error!(ErrorCode::MaxWalletExceeded));
+    }
+    
+    Ok(())
+}
+
+/// Cooldown between buys, escalated by the number of wallets the buyer is related to
+pub fn effective_buy_cooldown(base_cooldown: u32, related_wallet_count: usize) -> i64 {
+    let multiplier = (related_wallet_count as i64)
+        .saturating_add(1)
+        .min(MAX_COOLDOWN_MULTIPLIER);
+    
+    (base_cooldown as i64).saturating_mul(multiplier)
+}
+
+/// Check that enough time has passed since the wallet's or cluster's last trade
+pub fn check_buy_cooldown(
+    project: &TokenProject,
+    last_trade: i64,
+    related_wallet_count: usize,
+    now: i64
+) -> Result<()> {
+    // Cooldowns only apply during the curve phase
+    if project.is_graduated {
+        return Ok(());
+    }
+    
+    let base_cooldown = match project.buy_cooldown_secs {
+        Some(base_cooldown) => base_cooldown,
+        None => return Ok(()),
+    };
+    
+    // Wallets that have never traded have nothing to cool down from
+    if last_trade == 0 {
+        return Ok(());
+    }
+    
+    let cooldown = effective_buy_cooldown(base_cooldown, related_wallet_count);
+    if now.saturating_sub(last_trade) < cooldown {
+        return Err(error!(ErrorCode::BuyCooldownActive));
+    }
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::project_fixture;
+
+    #[test]
+    fn max_buy_caps_each_transaction() {
+        let mut project = project_fixture();
+        assert!(check_max_buy(&project, u64::MAX).is_ok());
+        
+        project.max_buy_per_tx = Some(50_000);
+        assert!(check_max_buy(&project, 49_999).is_ok());
+        assert!(check_max_buy(&project, 50_000).is_ok());
+        assert!(check_max_buy(&project, 50_001).is_err());
+        
+        project.is_graduated = true;
+        assert!(check_max_buy(&project, 50_001).is_ok());
+    }
+
+    #[test]
+    fn max_wallet_is_measured_against_the_graduation_supply() {
+        // 1 SOL cap at 1,000 lamports per token is a million tokens, 2% of it 20,000
+        let mut project = project_fixture();
+        assert!(check_max_wallet(&project, u64::MAX).is_ok());
+        
+        project.max_wallet_bps = Some(200);
+        assert_eq!(limit_reference_supply(project.supply, project.current_price, project.graduation_market_cap), 1_000_000);
+        assert!(check_max_wallet(&project, 19_999).is_ok());
+        assert!(check_max_wallet(&project, 20_000).is_ok());
+        assert!(check_max_wallet(&project, 20_001).is_err());
+        
+        // Past the graduation supply, the current supply takes over
+        project.supply = 5_000_000;
+        assert!(check_max_wallet(&project, 100_000).is_ok());
+        assert!(check_max_wallet(&project, 100_001).is_err());
+        
+        project.is_graduated = true;
+        assert!(check_max_wallet(&project, u64::MAX).is_ok());
+    }
+
+    #[test]
+    fn reference_supply_without_a_price_is_the_current_supply() {
+        assert_eq!(limit_reference_supply(42, 0, 1_000_000_000), 42);
+    }
+
+    #[test]
+    fn cooldown_escalates_with_related_wallets() {
+        assert_eq!(effective_buy_cooldown(30, 0), 30);
+        assert_eq!(effective_buy_cooldown(30, 2), 90);
+        assert_eq!(effective_buy_cooldown(30, 9), 300);
+        
+        // The multiplier stops at MAX_COOLDOWN_MULTIPLIER
+        assert_eq!(effective_buy_cooldown(30, 50), 300);
+        assert_eq!(effective_buy_cooldown(u32::MAX, 1_000), u32::MAX as i64 * MAX_COOLDOWN_MULTIPLIER);
+    }
+}
\ No newline at end of file
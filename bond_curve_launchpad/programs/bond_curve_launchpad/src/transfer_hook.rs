@@ -3,28 +3,32 @@ use anchor_lang::solana_program::{
     program::{invoke, invoke_signed},
     system_instruction,
 };
+use spl_tlv_account_resolution::{account::ExtraAccountMeta, pubkey_data::PubkeyData, seeds::Seed, state::ExtraAccountMetaList};
 use spl_token_2022::{
-    extension::{transfer_hook, BaseStateWithExtensions, StateWithExtensions},
-    state::{Account, Mint},
+    extension::{transfer_hook, StateWithExtensions},
+    state::{Account as TokenAccountState, Mint},
 };
 use spl_transfer_hook_interface::instruction::{ExecuteInstruction, TransferHookInstruction};
 
 use crate::bundle_detection;
-use crate::BundleTracker;
+use crate::{BundleCluster, BundleTracker, ClusterMembership, ErrorCode, TokenProject};
+use crate::trading_limits::check_max_wallet;
+use crate::exemptions::{has_exemption, load_exemption, EXEMPT_BUNDLING, EXEMPT_FEES, EXEMPT_LIMITS};
 
-/// Initialize the transfer hook extension on a token mint
+/// Initialize the close authority, transfer hook and permanent delegate extensions on an
+/// allocated but not yet initialized Token-2022 mint
 pub fn initialize_transfer_hook(
     mint: &AccountInfo,
-    authority: &AccountInfo,
+    authority: &Pubkey,
     program_id: &Pubkey,
+    permanent_delegate: &Pubkey,
     token_program: &AccountInfo,
 ) -> Result<()> {
-    // Create the instruction to enable the transfer hook
+    // Let the authority close the mint once its supply is zero
     let ix = spl_token_2022::instruction::initialize_mint_close_authority(
         token_program.key,
         mint.key,
-        Some(authority.key),
-        None,
+        Some(authority),
     )?;
 
     // Execute the instruction
@@ -32,17 +36,32 @@ pub fn initialize_transfer_hook(
         &ix,
         &[
             mint.clone(),
-            authority.clone(),
             token_program.clone(),
         ],
     )?;
 
     // Enable the transfer hook extension
-    let ix = spl_token_2022::instruction::initialize_transfer_hook(
+    let ix = spl_token_2022::extension::transfer_hook::instruction::initialize(
         token_program.key,
         mint.key,
-        Some(program_id),
-        authority.key,
+        Some(*authority),
+        Some(*program_id),
+    )?;
+
+    // Execute the instruction
+    invoke(
+        &ix,
+        &[
+            mint.clone(),
+            token_program.clone(),
+        ],
+    )?;
+
+    // Make the project PDA the permanent delegate so bundled tokens can be seized
+    let ix = spl_token_2022::instruction::initialize_permanent_delegate(
+        token_program.key,
+        mint.key,
+        permanent_delegate,
     )?;
 
     // Execute the instruction
@@ -50,7 +69,6 @@ pub fn initialize_transfer_hook(
         &ix,
         &[
             mint.clone(),
-            authority.clone(),
             token_program.clone(),
         ],
     )?;
@@ -58,6 +76,60 @@ pub fn initialize_transfer_hook(
     Ok(())
 }
 
+/// Index of the source wallet's cluster membership among the Execute instruction's accounts
+const MEMBERSHIP_INDEX: u8 = 9;
+
+/// Offset of `ClusterMembership::cluster`, after the discriminator, mint and wallet
+const MEMBERSHIP_CLUSTER_OFFSET: u8 = 8 + 32 + 32;
+
+/// Extra accounts Token-2022 passes to the hook on every transfer, in the order
+/// `process_execute_instruction` reads them. Indexes 0-4 are the source, mint,
+/// destination, owner and validation accounts of the Execute instruction.
+pub fn extra_account_metas() -> Result<Vec<ExtraAccountMeta>> {
+    // Token account owners sit at bytes 32..64 of the source (0) and destination (2)
+    let source_owner = Seed::AccountData { account_index: 0, data_index: 32, length: 32 };
+    let destination_owner = Seed::AccountData { account_index: 2, data_index: 32, length: 32 };
+    let mint = Seed::AccountKey { index: 1 };
+    
+    Ok(vec![
+        // Project, writable so transfers out of the creator's wallet can shrink its allocation
+        ExtraAccountMeta::new_with_seeds(&[Seed::Literal { bytes: b"project".to_vec() }, mint.clone()], false, true)?,
+        // Fee vault
+        ExtraAccountMeta::new_with_seeds(&[Seed::Literal { bytes: b"fee_vault".to_vec() }], false, false)?,
+        // Source wallet's bundle tracker
+        ExtraAccountMeta::new_with_seeds(
+            &[Seed::Literal { bytes: b"bundle".to_vec() }, mint.clone(), source_owner.clone()],
+            false,
+            false,
+        )?,
+        // Launchpad config
+        ExtraAccountMeta::new_with_seeds(&[Seed::Literal { bytes: b"config".to_vec() }], false, false)?,
+        // Source wallet's cluster membership
+        ExtraAccountMeta::new_with_seeds(
+            &[Seed::Literal { bytes: b"membership".to_vec() }, mint, source_owner.clone()],
+            false,
+            false,
+        )?,
+        // Allowlist entries for both sides of the transfer
+        ExtraAccountMeta::new_with_seeds(&[Seed::Literal { bytes: b"exempt".to_vec() }, source_owner], false, false)?,
+        ExtraAccountMeta::new_with_seeds(&[Seed::Literal { bytes: b"exempt".to_vec() }, destination_owner], false, false)?,
+        // The cluster the source wallet's membership points at
+        ExtraAccountMeta::new_with_pubkey_data(
+            &PubkeyData::AccountData { account_index: MEMBERSHIP_INDEX, data_index: MEMBERSHIP_CLUSTER_OFFSET },
+            false,
+            false,
+        )?,
+    ])
+}
+
+/// Write the extra account metas into a mint's validation account
+pub fn write_extra_account_metas(extra_account_meta_list: &AccountInfo) -> Result<()> {
+    let mut data = extra_account_meta_list.try_borrow_mut_data()?;
+    ExtraAccountMetaList::init::<ExecuteInstruction>(&mut data, &extra_account_metas()?)?;
+    
+    Ok(())
+}
+
 /// Process the transfer hook instruction
 pub fn process_transfer_hook(
     program_id: &Pubkey,
@@ -85,20 +157,25 @@ fn process_execute_instruction(
     accounts: &[AccountInfo],
     execute_instruction: ExecuteInstruction,
 ) -> Result<()> {
-    // Parse accounts
+    // Parse accounts in the transfer hook interface order
     let account_iter = &mut accounts.iter();
     
-    let mint_info = next_account_info(account_iter)?;
     let source_info = next_account_info(account_iter)?;
+    let mint_info = next_account_info(account_iter)?;
     let destination_info = next_account_info(account_iter)?;
-    let owner_info = next_account_info(account_iter)?;
-    let token_program_info = next_account_info(account_iter)?;
+    let _owner_info = next_account_info(account_iter)?;
+    let _extra_account_meta_list_info = next_account_info(account_iter)?;
     
-    // Try to get the optional accounts
+    // Extra accounts resolved from the validation account, see `extra_account_metas`.
+    // Wallets that have never traded have no tracker, membership or allowlist entry.
     let project_info = next_account_info(account_iter).ok();
     let fee_vault_info = next_account_info(account_iter).ok();
     let bundle_tracker_info = next_account_info(account_iter).ok();
     let config_info = next_account_info(account_iter).ok();
+    let membership_info = next_account_info(account_iter).ok();
+    let source_exempt_info = next_account_info(account_iter).ok();
+    let destination_exempt_info = next_account_info(account_iter).ok();
+    let cluster_info = next_account_info(account_iter).ok();
     
     // Verify the mint account
     let mint_data = mint_info.try_borrow_data()?;
@@ -114,7 +191,17 @@ fn process_execute_instruction(
     
     // Load the source account
     let source_data = source_info.try_borrow_data()?;
-    let source_account = StateWithExtensions::<Account>::unpack(&source_data)?;
+    let source_account = StateWithExtensions::<TokenAccountState>::unpack(&source_data)?;
+    
+    // Load the destination account
+    let destination_data = destination_info.try_borrow_data()?;
+    let destination_account = StateWithExtensions::<TokenAccountState>::unpack(&destination_data)?;
+    
+    // Look up allowlist entries for both sides of the transfer
+    let source_exemption = load_exemption(source_exempt_info, &source_account.base.owner, program_id)?;
+    let destination_exemption = load_exemption(destination_exempt_info, &destination_account.base.owner, program_id)?;
+    let source_owner = source_account.base.owner;
+    let destination_owner = destination_account.base.owner;
     
     // Check if this is a transfer from our launchpad
     let is_launchpad_transfer = is_from_launchpad(project_info, source_info);
@@ -123,34 +210,39 @@ fn process_execute_instruction(
     let amount = execute_instruction.amount;
     
     // Check for bundling if we have the necessary accounts
-    if let (Some(bundle_tracker), Some(config)) = (bundle_tracker_info, config_info) {
-        // Try to deserialize the bundle tracker account
-        if bundle_tracker.owner == program_id {
-            let bundle_tracker_data = bundle_tracker.try_borrow_data()?;
-            if !bundle_tracker_data.is_empty() {
-                // Deserialize the bundle tracker account
-                let bundle_tracker_account = Account::<BundleTracker>::try_from(bundle_tracker)?;
-                
-                // Check if the source wallet is part of a bundle
-                let is_bundling = bundle_tracker_account.is_bundling;
-                
-                if is_bundling {
-                    // If bundling is detected, apply 100% tax by preventing the transfer
-                    msg!("Bundling detected. Applying 100% tax.");
-                    
-                    // In a real implementation, we would:
-                    // 1. Cancel the current transfer
-                    // 2. Initiate a new transfer to burn the tokens or send to a fee account
-                    
-                    // For now, we'll just prevent the transfer
-                    return Err(error!(ErrorCode::BundlingDetected));
-                }
-            }
+    if config_info.is_some() {
+        // The source wallet is bundling if either its own tracker or its cluster is flagged,
+        // unless it is allowlisted
+        let is_bundling = !has_exemption(source_exemption.as_ref(), &source_owner, EXEMPT_BUNDLING)
+            && (is_tracker_flagged(bundle_tracker_info, program_id)?
+                || is_cluster_flagged(membership_info, cluster_info, &source_owner, program_id)?);
+        
+        if is_bundling {
+            // Apply the 100% tax by preventing the transfer until the tokens are
+            // seized, which burns them rather than transferring
+            msg!("Bundling detected. Applying 100% tax.");
+            return Err(error!(ErrorCode::BundlingDetected));
+        }
+    }
+    
+    // Enforce the project's max-wallet limit against the destination balance
+    if let Some(project) = project_info {
+        if !has_exemption(destination_exemption.as_ref(), &destination_owner, EXEMPT_LIMITS) {
+            check_destination_limit(project, mint_info.key, &destination_account.base, program_id)?;
         }
     }
     
+    // Tokens the creator moves out stop counting as its disclosed allocation
+    if let Some(project) = project_info {
+        update_creator_allocation(project, mint_info.key, &source_account.base, program_id)?;
+    }
+    
+    // Transfers to or from fee-exempt addresses skip the external fee
+    let is_fee_exempt = has_exemption(source_exemption.as_ref(), &source_owner, EXEMPT_FEES)
+        || has_exemption(destination_exemption.as_ref(), &destination_owner, EXEMPT_FEES);
+    
     // If this is not a launchpad transfer, apply the 2% fee
-    if !is_launchpad_transfer && fee_vault_info.is_some() {
+    if !is_launchpad_transfer && !is_fee_exempt && fee_vault_info.is_some() {
         // Calculate 2% fee
         let fee_amount = amount
             .checked_mul(200)
@@ -175,6 +267,93 @@ fn process_execute_instruction(
     Ok(())
 }
 
+/// Check the destination's balance after the transfer against the max-wallet limit
+fn check_destination_limit(
+    project_info: &AccountInfo,
+    mint: &Pubkey,
+    destination: &TokenAccountState,
+    program_id: &Pubkey,
+) -> Result<()> {
+    if project_info.owner != program_id {
+        return Ok(());
+    }
+    
+    let project = Account::<TokenProject>::try_from(project_info)?;
+    if project.mint != *mint {
+        return Ok(());
+    }
+    
+    // Accounts owned by the project itself, such as the penalty vault, are not wallets
+    if destination.owner == *project_info.key {
+        return Ok(());
+    }
+    
+    // The token program has already credited the destination when the hook runs
+    check_max_wallet(&project, destination.amount)
+}
+
+/// Cap the creator's disclosed allocation at the balance left in the source after the
+/// transfer. The project is only written when the allocation actually shrinks.
+fn update_creator_allocation(
+    project_info: &AccountInfo,
+    mint: &Pubkey,
+    source: &TokenAccountState,
+    program_id: &Pubkey,
+) -> Result<()> {
+    if project_info.owner != program_id {
+        return Ok(());
+    }
+    
+    let mut project = Account::<TokenProject>::try_from(project_info)?;
+    if project.mint != *mint || source.owner != project.creator || project.creator_allocation <= source.amount {
+        return Ok(());
+    }
+    
+    // The token program has already debited the source when the hook runs
+    bundle_detection::cap_creator_allocation(&mut project, &source.owner, source.amount);
+    project.exit(program_id)
+}
+
+/// Check whether a wallet's own bundle tracker is flagged
+fn is_tracker_flagged(bundle_tracker_info: Option<&AccountInfo>, program_id: &Pubkey) -> Result<bool> {
+    let Some(bundle_tracker) = bundle_tracker_info else {
+        return Ok(false);
+    };
+    
+    if bundle_tracker.owner != program_id || bundle_tracker.data_is_empty() {
+        return Ok(false);
+    }
+    
+    // Expired flags no longer block transfers
+    let bundle_tracker_account = Account::<BundleTracker>::try_from(bundle_tracker)?;
+    Ok(bundle_tracker_account.is_bundling
+        && !bundle_detection::is_flag_expired(&bundle_tracker_account, Clock::get()?.unix_timestamp))
+}
+
+/// Check whether the cluster the wallet belongs to is flagged
+fn is_cluster_flagged(
+    membership_info: Option<&AccountInfo>,
+    cluster_info: Option<&AccountInfo>,
+    wallet: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<bool> {
+    let (Some(membership), Some(cluster)) = (membership_info, cluster_info) else {
+        return Ok(false);
+    };
+    
+    if membership.owner != program_id || cluster.owner != program_id {
+        return Ok(false);
+    }
+    
+    let membership_account = Account::<ClusterMembership>::try_from(membership)?;
+    if membership_account.wallet != *wallet || membership_account.cluster != *cluster.key {
+        return Ok(false);
+    }
+    
+    let cluster_account = Account::<BundleCluster>::try_from(cluster)?;
+    Ok(cluster_account.is_bundling)
+}
+
 /// Check if a transfer is originating from our launchpad
 fn is_from_launchpad(project_info: Option<&AccountInfo>, source_info: &AccountInfo) -> bool {
     if let Some(project) = project_info {
@@ -0,0 +1,148 @@
+use anchor_lang::prelude::*;
+
+use crate::{ErrorCode, VestingEscrow, VestingSchedule};
+use crate::trading_limits::limit_reference_supply;
+
+/// Largest creator allocation, in basis points of the graduation supply
+pub const MAX_PREMINT_BPS: u16 = 2000;
+
+/// Largest creator allocation a project can mint: a share of the supply it would need
+/// at its initial price to reach the graduation market cap
+pub fn max_premint_supply(initial_price: u64, graduation_market_cap: u64) -> Result<u64> {
+    let graduation_supply = limit_reference_supply(0, initial_price, graduation_market_cap);
+    
+    let max_premint = (graduation_supply as u128)
+        .checked_mul(MAX_PREMINT_BPS as u128)
+        .ok_or(error!(ErrorCode::MathOverflow))?
+        .checked_div(10000)
+        .ok_or(error!(ErrorCode::DivisionByZero))?;
+    
+    Ok(max_premint as u64)
+}
+
+/// Check a creator vesting schedule before the allocation is minted
+pub fn validate_vesting_schedule(schedule: &VestingSchedule, max_amount: u64) -> Result<()> {
+    if schedule.amount > max_amount {
+        return Err(error!(ErrorCode::VestingAllocationTooLarge));
+    }
+    
+    if schedule.amount == 0
+        || schedule.duration_secs <= 0
+        || schedule.cliff_secs < 0
+        || schedule.cliff_secs > schedule.duration_secs
+    {
+        return Err(error!(ErrorCode::InvalidVestingSchedule));
+    }
+    
+    Ok(())
+}
+
+/// Start a vesting escrow for `beneficiary` at `now`
+pub fn initialize_vesting_escrow(
+    vesting_escrow: &mut VestingEscrow,
+    mint: &Pubkey,
+    beneficiary: &Pubkey,
+    schedule: &VestingSchedule,
+    now: i64,
+) -> Result<()> {
+    vesting_escrow.mint = *mint;
+    vesting_escrow.beneficiary = *beneficiary;
+    vesting_escrow.total_amount = schedule.amount;
+    vesting_escrow.claimed_amount = 0;
+    vesting_escrow.start_time = now;
+    vesting_escrow.cliff_time = now
+        .checked_add(schedule.cliff_secs)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+    vesting_escrow.end_time = now
+        .checked_add(schedule.duration_secs)
+        .ok_or(error!(ErrorCode::MathOverflow))?;
+    
+    Ok(())
+}
+
+/// Amount unlocked at `now`: nothing before the cliff, then linear from the start
+/// time until everything unlocks at the end time
+pub fn vested_amount(vesting_escrow: &VestingEscrow, now: i64) -> Result<u64> {
+    if now < vesting_escrow.cliff_time {
+        return Ok(0);
+    }
+    
+    if now >= vesting_escrow.end_time {
+        return Ok(vesting_escrow.total_amount);
+    }
+    
+    let elapsed = now.saturating_sub(vesting_escrow.start_time) as u128;
+    let duration = vesting_escrow.end_time.saturating_sub(vesting_escrow.start_time) as u128;
+    
+    let vested = (vesting_escrow.total_amount as u128)
+        .checked_mul(elapsed)
+        .ok_or(error!(ErrorCode::MathOverflow))?
+        .checked_div(duration)
+        .ok_or(error!(ErrorCode::DivisionByZero))?;
+    
+    Ok(vested as u64)
+}
+
+/// Amount the beneficiary can claim at `now`
+pub fn claimable_amount(vesting_escrow: &VestingEscrow, now: i64) -> Result<u64> {
+    vested_amount(vesting_escrow, now)?
+        .checked_sub(vesting_escrow.claimed_amount)
+        .ok_or(error!(ErrorCode::MathOverflow))
+}
+
+/// Address of a project's vesting escrow, which bundle detection ignores
+pub fn vesting_escrow_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"vesting", mint.as_ref()], &crate::ID).0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn escrow_fixture(total_amount: u64) -> VestingEscrow {
+        VestingEscrow {
+            mint: Pubkey::new_unique(),
+            beneficiary: Pubkey::new_unique(),
+            total_amount,
+            claimed_amount: 0,
+            start_time: 1_000,
+            cliff_time: 1_100,
+            end_time: 1_300,
+        }
+    }
+
+    #[test]
+    fn vests_linearly_from_the_cliff_to_the_end() {
+        let vesting_escrow = escrow_fixture(1_000);
+        
+        assert_eq!(vested_amount(&vesting_escrow, 999).unwrap(), 0);
+        assert_eq!(vested_amount(&vesting_escrow, 1_099).unwrap(), 0);
+        // At the cliff everything since the start time unlocks at once
+        assert_eq!(vested_amount(&vesting_escrow, 1_100).unwrap(), 333);
+        assert_eq!(vested_amount(&vesting_escrow, 1_299).unwrap(), 996);
+        assert_eq!(vested_amount(&vesting_escrow, 1_300).unwrap(), 1_000);
+        assert_eq!(vested_amount(&vesting_escrow, i64::MAX).unwrap(), 1_000);
+    }
+
+    #[test]
+    fn claimable_excludes_what_was_claimed() {
+        let mut vesting_escrow = escrow_fixture(1_000);
+        vesting_escrow.claimed_amount = 333;
+        
+        assert_eq!(claimable_amount(&vesting_escrow, 1_200).unwrap(), 333);
+        assert_eq!(claimable_amount(&vesting_escrow, 1_300).unwrap(), 667);
+    }
+
+    #[test]
+    fn caps_the_allocation_at_a_share_of_the_graduation_supply() {
+        // 1 SOL cap at 1,000 lamports per token is a million tokens
+        let max_premint = max_premint_supply(1_000, 1_000_000_000).unwrap();
+        assert_eq!(max_premint, 200_000);
+        
+        let schedule = VestingSchedule { amount: max_premint, cliff_secs: 0, duration_secs: 100 };
+        assert!(validate_vesting_schedule(&schedule, max_premint).is_ok());
+        
+        let schedule = VestingSchedule { amount: max_premint + 1, ..schedule };
+        assert!(validate_vesting_schedule(&schedule, max_premint).is_err());
+    }
+}
@@ -3,8 +3,11 @@ use anchor_lang::solana_program::{
     program::{invoke, invoke_signed},
     system_instruction,
 };
+use anchor_spl::associated_token::{self, get_associated_token_address};
 use anchor_spl::token::{Token, TokenAccount};
 
+use crate::ErrorCode;
+
 /// Native SOL mint address (WSOL)
 pub const WSOL_MINT: &str = "So11111111111111111111111111111111111111112";
 
@@ -89,6 +92,102 @@ pub fn unwrap_sol(
     Ok(())
 }
 
+/// Wrap SOL into a WSOL associated token account owned by a PDA.
+///
+/// The account is created if it does not exist yet, otherwise it is topped up. The
+/// lamports come from `source`, which is either an account owned by this program or a
+/// system account that `signer_seeds` sign for.
+pub fn wrap_sol_signed<'info>(
+    amount: u64,
+    source: &AccountInfo<'info>,
+    owner: &AccountInfo<'info>,
+    wsol_account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    wsol_mint: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    associated_token_program: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    if *wsol_account.key != get_associated_token_address(owner.key, &get_wsol_mint()) {
+        return Err(error!(ErrorCode::InvalidWsolAccount));
+    }
+
+    // Create the owner's WSOL account on first use
+    if wsol_account.data_is_empty() {
+        associated_token::create_idempotent(CpiContext::new(
+            associated_token_program.clone(),
+            associated_token::Create {
+                payer: payer.clone(),
+                associated_token: wsol_account.clone(),
+                authority: owner.clone(),
+                mint: wsol_mint.clone(),
+                system_program: system_program.clone(),
+                token_program: token_program.clone(),
+            },
+        ))?;
+    }
+
+    // Program-owned accounts hold data, so their lamports are moved directly
+    if source.owner == &crate::ID {
+        **source.try_borrow_mut_lamports()? = source
+            .lamports()
+            .checked_sub(amount)
+            .ok_or(error!(ErrorCode::InsufficientFunds))?;
+
+        **wsol_account.try_borrow_mut_lamports()? = wsol_account
+            .lamports()
+            .checked_add(amount)
+            .ok_or(error!(ErrorCode::MathOverflow))?;
+    } else {
+        invoke_signed(
+            &system_instruction::transfer(source.key, wsol_account.key, amount),
+            &[source.clone(), wsol_account.clone(), system_program.clone()],
+            signer_seeds,
+        )?;
+    }
+
+    // Sync native account so the token balance reflects the new lamports
+    invoke(
+        &spl_token::instruction::sync_native(
+            &spl_token::ID,
+            wsol_account.key,
+        )?,
+        &[wsol_account.clone(), token_program.clone()],
+    )?;
+
+    Ok(())
+}
+
+/// Unwrap a PDA-owned WSOL account, sending all its SOL to `destination`
+pub fn unwrap_sol_signed<'info>(
+    wsol_account: &AccountInfo<'info>,
+    owner: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    // Close the account with the PDA signing as owner
+    invoke_signed(
+        &spl_token::instruction::close_account(
+            &spl_token::ID,
+            wsol_account.key,
+            destination.key,
+            owner.key,
+            &[],
+        )?,
+        &[
+            wsol_account.clone(),
+            destination.clone(),
+            owner.clone(),
+            token_program.clone(),
+        ],
+        signer_seeds,
+    )?;
+
+    Ok(())
+}
+
 /// Get the WSOL mint pubkey
 pub fn get_wsol_mint() -> Pubkey {
     spl_token::native_mint::id()